@@ -1,20 +1,40 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+    to_binary, Addr, Api, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use operations_core::{
+    amount::{require_balance, require_holder},
+    instantiate_entry_point, migrate_entry_point,
+    operation::{build_batch_messages, build_messages},
+    ownership::update_config,
+    AmountSource, AmountSpec, Operation,
 };
 use oraidex::asset::{Asset, AssetInfo};
 
+use oraidex::oracle::OracleContract;
 use oraidex::pair::PairExecuteMsg;
-use oraidex::querier::{query_staking_amount, query_token_balance};
+use oraidex::querier::{query_staking_amount, simulate_swap_operations};
 use oraidex::router::{OraiswapExecuteMsg, SwapOperation};
 use oraidex::staking::StakingExecuteMsg;
+use oraidex::tax::{amount_after_tax, query_messages_tax, query_tax, MessagesWithTaxResponse};
 // use cw2::set_contract_version;
 
 use crate::error::ContractError;
-use crate::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, Operations, QueryMsg};
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, Operations, QueryMsg,
+    SimulateSwapResponse,
+};
+use crate::position::query_position;
+use crate::registry::{
+    assert_registered_pair, assert_registered_staking_pool, execute_deregister_pair,
+    execute_register_pair, query_pair, query_pairs,
+};
+use crate::route::query_swap_best_route_msg;
 use crate::state::{Config, CONFIG};
+use crate::valuation::query_valuation;
+use crate::zap::{query_compound_msg, query_zap_in_msg, query_zap_out_msg};
 
 use cw20::Cw20ExecuteMsg;
 
@@ -24,23 +44,23 @@ const CONTRACT_NAME: &str = "crates.io:smart-wallet";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 */
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    msg: InstantiateMsg,
-) -> Result<Response, ContractError> {
-    CONFIG.save(
-        deps.storage,
-        &Config {
-            owner: deps.api.addr_canonicalize(msg.owner.as_str())?,
-            oraiswap_router: deps.api.addr_canonicalize(msg.oraiswap_router.as_str())?,
-            oraiswap_staking: deps.api.addr_canonicalize(msg.oraiswap_staking.as_str())?,
-        },
-    )?;
+instantiate_entry_point!(InstantiateMsg, CONFIG, config_from_msg);
 
-    Ok(Response::default())
+fn config_from_msg(api: &dyn Api, msg: InstantiateMsg) -> StdResult<Config> {
+    Ok(Config {
+        owner: api.addr_canonicalize(msg.owner.as_str())?,
+        oraiswap_router: api.addr_canonicalize(msg.oraiswap_router.as_str())?,
+        oraiswap_staking: api.addr_canonicalize(msg.oraiswap_staking.as_str())?,
+        oraiswap_factory: msg
+            .oraiswap_factory
+            .map(|factory| api.addr_canonicalize(factory.as_str()))
+            .transpose()?,
+        oraiswap_oracle: msg
+            .oraiswap_oracle
+            .map(|oracle| api.addr_canonicalize(oracle.as_str()))
+            .transpose()?,
+        require_registered_pair: msg.require_registered_pair.unwrap_or(false),
+    })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -55,10 +75,30 @@ pub fn execute(
             owner,
             oraiswap_router,
             oraiswap_staking,
-        } => execute_update_config(deps, env, info, owner, oraiswap_router, oraiswap_staking),
+            oraiswap_factory,
+            oraiswap_oracle,
+            require_registered_pair,
+        } => execute_update_config(
+            deps,
+            env,
+            info,
+            owner,
+            oraiswap_router,
+            oraiswap_staking,
+            oraiswap_factory,
+            oraiswap_oracle,
+            require_registered_pair,
+        ),
+        ExecuteMsg::RegisterPair { pair_contract } => {
+            execute_register_pair(deps, info, pair_contract)
+        }
+        ExecuteMsg::DeregisterPair { asset_infos } => {
+            execute_deregister_pair(deps, info, asset_infos)
+        }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
     _env: Env,
@@ -66,124 +106,396 @@ pub fn execute_update_config(
     owner: Option<Addr>,
     oraiswap_router: Option<Addr>,
     oraiswap_staking: Option<Addr>,
+    oraiswap_factory: Option<Addr>,
+    oraiswap_oracle: Option<Addr>,
+    require_registered_pair: Option<bool>,
 ) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
-    if deps.api.addr_humanize(&config.owner)? != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    Ok(update_config(
+        deps,
+        &info,
+        &CONFIG,
+        owner,
+        |api, config| {
+            if let Some(oraiswap_router) = oraiswap_router {
+                config.oraiswap_router = api.addr_canonicalize(oraiswap_router.as_str())?;
+            }
 
-    if let Some(owner) = owner {
-        config.owner = deps.api.addr_canonicalize(owner.as_str())?;
-    }
+            if let Some(oraiswap_staking) = oraiswap_staking {
+                config.oraiswap_staking = api.addr_canonicalize(oraiswap_staking.as_str())?;
+            }
 
-    if let Some(oraiswap_router) = oraiswap_router {
-        config.oraiswap_router = deps.api.addr_canonicalize(oraiswap_router.as_str())?;
-    }
+            if let Some(oraiswap_factory) = oraiswap_factory {
+                config.oraiswap_factory = Some(api.addr_canonicalize(oraiswap_factory.as_str())?);
+            }
 
-    if let Some(oraiswap_staking) = oraiswap_staking {
-        config.oraiswap_staking = deps.api.addr_canonicalize(oraiswap_staking.as_str())?;
-    }
-    CONFIG.save(deps.storage, &config)?;
+            if let Some(oraiswap_oracle) = oraiswap_oracle {
+                config.oraiswap_oracle = Some(api.addr_canonicalize(oraiswap_oracle.as_str())?);
+            }
 
-    Ok(Response::new().add_attribute("action", "update_config"))
+            if let Some(require_registered_pair) = require_registered_pair {
+                config.require_registered_pair = require_registered_pair;
+            }
+            Ok(())
+        },
+    )?)
 }
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Messages { msg } => match from_binary(&msg) {
-            Ok(Operations::SwapOperations {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Messages { msg } => to_binary(&build_messages::<Operations>(deps, env, &msg)?),
+        QueryMsg::BatchMessages { msgs } => to_binary(&build_batch_messages(
+            deps,
+            env,
+            &msgs,
+            build_messages::<Operations>,
+        )?),
+        QueryMsg::SimulateSwap {
+            offer_amount,
+            operations,
+        } => to_binary(&query_simulate_swap(deps, offer_amount, operations)?),
+        QueryMsg::Position { owner, asset_info } => {
+            to_binary(&query_position(deps, owner, asset_info)?)
+        }
+        QueryMsg::Valuation {
+            owner,
+            assets,
+            quote_denom,
+        } => to_binary(&query_valuation(deps, owner, assets, quote_denom)?),
+        QueryMsg::Tax { asset } => {
+            to_binary(&query_tax(&deps.querier, &load_oracle(deps)?, &asset)?)
+        }
+        QueryMsg::MessagesWithTax { msg } => {
+            let messages = build_messages::<Operations>(deps, env, &msg)?;
+            let tax = query_messages_tax(&deps.querier, &load_oracle(deps)?, &messages)?;
+            to_binary(&MessagesWithTaxResponse { messages, tax })
+        }
+        QueryMsg::Pair { asset_infos } => to_binary(&query_pair(deps, asset_infos)?),
+        QueryMsg::Pairs { start_after, limit } => {
+            to_binary(&query_pairs(deps, start_after, limit)?)
+        }
+    }
+}
+
+impl Operation for Operations {
+    fn build(self, deps: Deps, env: Env) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            Operations::SwapOperations {
                 executor_addr,
                 sender,
                 amount,
                 operations,
                 minimum_receive,
+                max_slippage,
                 to,
-            }) => to_binary(&query_swap_operations_msg(
-                deps,
-                env,
+                deduct_tax,
+            } => {
+                let amount = match operations.first().map(|op| op.get_offer_asset_info()) {
+                    Some(AssetInfo::NativeToken { denom }) if deduct_tax => {
+                        let amount = AmountSource::native_balance(
+                            amount,
+                            denom.clone(),
+                            Some(sender.clone()),
+                        )?
+                        .resolve(&deps.querier)?;
+                        let info = AssetInfo::NativeToken { denom };
+                        Some(AmountSpec::Exact(query_amount_after_tax(
+                            deps, &info, amount, true,
+                        )?))
+                    }
+                    _ => amount,
+                };
+                query_swap_operations_msg(
+                    deps,
+                    env,
+                    executor_addr,
+                    sender,
+                    amount,
+                    operations,
+                    SwapOptions {
+                        minimum_receive,
+                        max_slippage,
+                        to,
+                    },
+                )
+            }
+            Operations::SwapBestRoute {
                 executor_addr,
                 sender,
-                amount,
-                operations,
+                offer_asset,
+                ask_asset_info,
+                max_hops,
                 minimum_receive,
+                max_slippage,
                 to,
-            )?),
-            Ok(Operations::ProvideLiquidity {
+                deduct_tax,
+            } => {
+                let offer_asset = Asset {
+                    amount: query_amount_after_tax(
+                        deps,
+                        &offer_asset.info,
+                        offer_asset.amount,
+                        deduct_tax,
+                    )?,
+                    info: offer_asset.info,
+                };
+                query_swap_best_route_msg(
+                    deps,
+                    env,
+                    executor_addr,
+                    sender,
+                    offer_asset,
+                    ask_asset_info,
+                    max_hops,
+                    minimum_receive,
+                    max_slippage,
+                    to,
+                )
+            }
+            Operations::ProvideLiquidity {
+                executor_addr,
+                sender,
                 pair_contract,
                 assets,
                 slippage_tolerance,
                 receiver,
-            }) => to_binary(&query_provide_liquidity_msg(
-                deps,
-                env,
+                deduct_tax,
+            } => {
+                assert_registered_pair(deps, &pair_contract, None)?;
+                let [asset_0, asset_1] = assets;
+                let assets = [
+                    Asset {
+                        amount: query_amount_after_tax(
+                            deps,
+                            &asset_0.info,
+                            asset_0.amount,
+                            deduct_tax,
+                        )?,
+                        info: asset_0.info,
+                    },
+                    Asset {
+                        amount: query_amount_after_tax(
+                            deps,
+                            &asset_1.info,
+                            asset_1.amount,
+                            deduct_tax,
+                        )?,
+                        info: asset_1.info,
+                    },
+                ];
+                query_provide_liquidity_msg(
+                    deps,
+                    env,
+                    executor_addr,
+                    sender,
+                    pair_contract,
+                    assets,
+                    slippage_tolerance,
+                    receiver,
+                )
+            }
+            Operations::ZapIn {
+                executor_addr,
                 pair_contract,
-                assets,
+                offer_asset,
                 slippage_tolerance,
-                receiver,
-            )?),
-            Ok(Operations::WithdrawLiquidity {
+                bond,
+                deduct_tax,
+            } => {
+                assert_registered_pair(deps, &pair_contract, None)?;
+                query_zap_in_msg(
+                    deps,
+                    env,
+                    executor_addr,
+                    pair_contract,
+                    offer_asset,
+                    slippage_tolerance,
+                    bond,
+                    deduct_tax,
+                )
+            }
+            Operations::WithdrawLiquidity {
                 sender,
                 pair_contract,
                 lp_token,
                 amount,
-            }) => to_binary(&query_withdraw_liquidity_msg(
-                deps,
-                env,
-                sender,
+            } => {
+                assert_registered_pair(deps, &pair_contract, Some(&lp_token))?;
+                query_withdraw_liquidity_msg(deps, env, sender, pair_contract, lp_token, amount)
+            }
+            Operations::ZapOut {
+                executor_addr,
                 pair_contract,
                 lp_token,
                 amount,
-            )?),
-            Ok(Operations::Bond {
+                target_asset_info,
+                minimum_receive,
+                unbond_first,
+                slippage_tolerance,
+            } => {
+                assert_registered_pair(deps, &pair_contract, Some(&lp_token))?;
+                query_zap_out_msg(
+                    deps,
+                    env,
+                    executor_addr,
+                    pair_contract,
+                    lp_token,
+                    amount,
+                    target_asset_info,
+                    minimum_receive,
+                    unbond_first,
+                    slippage_tolerance,
+                )
+            }
+            Operations::Compound {
+                executor_addr,
+                asset_info,
+                pair_contract,
+                slippage_tolerance,
+            } => {
+                assert_registered_pair(deps, &pair_contract, None)?;
+                query_compound_msg(
+                    deps,
+                    env,
+                    executor_addr,
+                    asset_info,
+                    pair_contract,
+                    slippage_tolerance,
+                )
+            }
+            Operations::Bond {
                 sender,
                 lp_token,
                 asset_info,
                 amount,
-            }) => to_binary(&query_bond_msg(
-                deps, env, sender, lp_token, asset_info, amount,
-            )?),
-            Ok(Operations::Unbond {
+            } => {
+                assert_registered_staking_pool(deps, &asset_info, Some(&lp_token))?;
+                query_bond_msg(deps, env, sender, lp_token, asset_info, amount)
+            }
+            Operations::Unbond {
                 sender,
                 asset_info,
                 amount,
-            }) => to_binary(&query_unbond_msg(deps, env, sender, asset_info, amount)?),
-            Ok(Operations::Withdraw { asset_info }) => {
-                to_binary(&query_withdraw_msg(deps, env, asset_info)?)
+            } => {
+                assert_registered_staking_pool(deps, &asset_info, None)?;
+                query_unbond_msg(deps, env, sender, asset_info, amount)
             }
-            _ => Err(StdError::NotFound {
-                kind: "Messages not found".to_string(),
-            }),
-        },
-        QueryMsg::Config {} => to_binary(&query_config(deps, env)?),
+            Operations::Withdraw { asset_info } => query_withdraw_msg(deps, env, asset_info),
+        }
     }
 }
 
-pub fn query_config(deps: Deps, _env: Env) -> StdResult<ConfigResponse> {
+pub fn load_oracle(deps: Deps) -> StdResult<OracleContract> {
+    let config = CONFIG.load(deps.storage)?;
+    OracleContract::from_config(deps.api, config.oraiswap_oracle.as_ref())
+}
+
+/// `amount` of `asset_info` net of the native transfer tax when `deduct_tax` is set.
+pub fn query_amount_after_tax(
+    deps: Deps,
+    asset_info: &AssetInfo,
+    amount: Uint128,
+    deduct_tax: bool,
+) -> StdResult<Uint128> {
+    amount_after_tax(
+        &deps.querier,
+        || load_oracle(deps),
+        asset_info,
+        amount,
+        deduct_tax,
+    )
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
     Ok(ConfigResponse {
         owner: deps.api.addr_humanize(&config.owner)?,
         oraiswap_router: deps.api.addr_humanize(&config.oraiswap_router)?,
         oraiswap_staking: deps.api.addr_humanize(&config.oraiswap_staking)?,
+        oraiswap_factory: config
+            .oraiswap_factory
+            .map(|factory| deps.api.addr_humanize(&factory))
+            .transpose()?,
+        oraiswap_oracle: config
+            .oraiswap_oracle
+            .map(|oracle| deps.api.addr_humanize(&oracle))
+            .transpose()?,
+        require_registered_pair: config.require_registered_pair,
     })
 }
 
+pub fn query_simulate_swap(
+    deps: Deps,
+    offer_amount: Uint128,
+    operations: Vec<SwapOperation>,
+) -> StdResult<SimulateSwapResponse> {
+    if operations.is_empty() {
+        return Err(StdError::GenericErr {
+            msg: "Swap operations is empty!!!".to_string(),
+        });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let swap_router = deps.api.addr_humanize(&config.oraiswap_router)?;
+
+    // the router only reports the final amount, so walk the route one hop at
+    // a time, each hop offering what the previous one returned
+    let mut amounts: Vec<Uint128> = Vec::with_capacity(operations.len());
+    let mut amount = offer_amount;
+    for operation in operations {
+        amount =
+            simulate_swap_operations(&deps.querier, swap_router.clone(), amount, vec![operation])?;
+        amounts.push(amount);
+    }
+
+    Ok(SimulateSwapResponse {
+        return_amount: amount,
+        amounts,
+    })
+}
+
+/// The stricter of the given `minimum_receive` and the simulated return
+/// reduced by `max_slippage`.
+fn query_minimum_receive(
+    deps: Deps,
+    swap_router: &Addr,
+    offer_amount: Uint128,
+    operations: &[SwapOperation],
+    minimum_receive: Option<Uint128>,
+    max_slippage: Option<Decimal>,
+) -> StdResult<Option<Uint128>> {
+    let max_slippage = match max_slippage {
+        Some(max_slippage) => max_slippage,
+        None => return Ok(minimum_receive),
+    };
+    if max_slippage > Decimal::one() {
+        return Err(StdError::generic_err("Max slippage must not exceed 1"));
+    }
+
+    let expected = simulate_swap_operations(
+        &deps.querier,
+        swap_router.clone(),
+        offer_amount,
+        operations.to_vec(),
+    )?;
+    let derived = expected * (Decimal::one() - max_slippage);
+
+    Ok(Some(
+        minimum_receive.map_or(derived, |minimum| minimum.max(derived)),
+    ))
+}
+
 pub fn query_bond_msg(
     deps: Deps,
     _env: Env,
     sender: Option<Addr>,
     lp_token: Addr,
     asset_info: AssetInfo,
-    amount: Option<Uint128>,
+    amount: Option<AmountSpec>,
 ) -> StdResult<Vec<CosmosMsg>> {
-    if sender.is_none() && amount.is_none() {
-        return Err(StdError::GenericErr {
-            msg: "Sender and amount is none!!!".to_string(),
-        });
-    }
-
-    let amount = amount.unwrap_or_else(|| {
-        query_token_balance(&deps.querier, lp_token.clone(), sender.unwrap()).unwrap()
-    });
+    let amount =
+        AmountSource::token_balance(amount, lp_token.clone(), sender)?.resolve(&deps.querier)?;
 
     let config = CONFIG.load(deps.storage)?;
     let msg = CosmosMsg::Wasm(WasmMsg::Execute {
@@ -201,29 +513,43 @@ pub fn query_bond_msg(
     Ok(vec![msg])
 }
 
+/// `amount` applied to the LP `sender` has bonded for `asset_info`.
+pub fn query_bonded_amount(
+    deps: Deps,
+    sender: Option<Addr>,
+    asset_info: &AssetInfo,
+    amount: Option<AmountSpec>,
+) -> StdResult<Uint128> {
+    match amount.unwrap_or_default() {
+        AmountSpec::Exact(amount) => Ok(amount),
+        spec => {
+            let config = CONFIG.load(deps.storage)?;
+            let sender = require_holder(sender)?;
+            let bond_amount = query_staking_amount(
+                &deps.querier,
+                deps.api.addr_humanize(&config.oraiswap_staking)?,
+                sender.clone(),
+                asset_info.clone(),
+            )?;
+            Ok(spec.apply(require_balance(
+                bond_amount,
+                asset_info.to_string(),
+                &sender,
+            )?)?)
+        }
+    }
+}
+
 pub fn query_unbond_msg(
     deps: Deps,
     _env: Env,
     sender: Option<Addr>,
     asset_info: AssetInfo,
-    amount: Option<Uint128>,
+    amount: Option<AmountSpec>,
 ) -> StdResult<Vec<CosmosMsg>> {
-    if sender.is_none() && amount.is_none() {
-        return Err(StdError::GenericErr {
-            msg: "Sender and amount is none!!!".to_string(),
-        });
-    }
     let config = CONFIG.load(deps.storage)?;
 
-    let amount = amount.unwrap_or_else(|| {
-        query_staking_amount(
-            &deps.querier,
-            deps.api.addr_humanize(&config.oraiswap_staking).unwrap(),
-            sender.unwrap(),
-            asset_info.clone(),
-        )
-        .unwrap()
-    });
+    let amount = query_bonded_amount(deps, sender, &asset_info, amount)?;
     let msg = CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: deps
             .api
@@ -258,17 +584,10 @@ pub fn query_withdraw_liquidity_msg(
     sender: Option<Addr>,
     pair_contract: Addr,
     lp_token: Addr,
-    amount: Option<Uint128>,
+    amount: Option<AmountSpec>,
 ) -> StdResult<Vec<CosmosMsg>> {
-    if sender.is_none() && amount.is_none() {
-        return Err(StdError::GenericErr {
-            msg: "Sender and amount is none!!!".to_string(),
-        });
-    }
-
-    let amount = amount.unwrap_or_else(|| {
-        query_token_balance(&deps.querier, lp_token.clone(), sender.unwrap()).unwrap()
-    });
+    let amount =
+        AmountSource::token_balance(amount, lp_token.clone(), sender)?.resolve(&deps.querier)?;
     let msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: lp_token.to_string(),
         msg: to_binary(&Cw20ExecuteMsg::Send {
@@ -280,25 +599,67 @@ pub fn query_withdraw_liquidity_msg(
     });
     Ok(vec![msg])
 }
+/// Native legs are attached as funds, cw20 legs are approved for the pair and,
+/// when `sender` is set and differs from `executor_addr`, pulled from `sender`
+/// first
+#[allow(clippy::too_many_arguments)]
 pub fn query_provide_liquidity_msg(
     _deps: Deps,
     _env: Env,
+    executor_addr: Option<Addr>,
+    sender: Option<Addr>,
     pair_contract: Addr,
     assets: [Asset; 2],
     slippage_tolerance: Option<Decimal>,
     receiver: Option<Addr>,
 ) -> StdResult<Vec<CosmosMsg>> {
+    // the executor the cw20 legs are pulled into, if pulled at all
+    let pull = match (executor_addr, sender) {
+        (Some(executor_addr), Some(sender)) if sender != executor_addr => {
+            Some((executor_addr, sender))
+        }
+        (None, Some(_)) => {
+            return Err(StdError::generic_err(
+                "executor_addr is required to pull the assets from sender",
+            ))
+        }
+        _ => None,
+    };
+
+    let mut messages: Vec<CosmosMsg> = vec![];
     let mut funds: Vec<Coin> = vec![];
     for asset in &assets {
-        if let AssetInfo::NativeToken { denom } = &asset.info {
-            funds.push(Coin {
+        match &asset.info {
+            AssetInfo::NativeToken { denom } => funds.push(Coin {
                 denom: denom.clone(),
                 amount: asset.amount,
-            });
+            }),
+            AssetInfo::Token { contract_addr } => {
+                if let Some((executor_addr, sender)) = &pull {
+                    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: contract_addr.to_string(),
+                        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                            owner: sender.to_string(),
+                            recipient: executor_addr.to_string(),
+                            amount: asset.amount,
+                        })?,
+                        funds: vec![],
+                    }));
+                }
+                messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: pair_contract.to_string(),
+                        amount: asset.amount,
+                        expires: None,
+                    })?,
+                    funds: vec![],
+                }));
+            }
         }
     }
 
-    let msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: pair_contract.to_string(),
         msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
             assets,
@@ -306,20 +667,34 @@ pub fn query_provide_liquidity_msg(
             receiver,
         })?,
         funds,
-    });
-    Ok(vec![msg])
+    }));
+    Ok(messages)
+}
+
+/// Floor on the output of a swap and where it goes, the executor when `to`
+/// is unset.
+#[derive(Default)]
+pub struct SwapOptions {
+    pub minimum_receive: Option<Uint128>,
+    pub max_slippage: Option<Decimal>,
+    pub to: Option<Addr>,
 }
+
 pub fn query_swap_operations_msg(
     deps: Deps,
     _env: Env,
     executor_addr: Addr,
     sender: Addr,
-    amount: Option<Uint128>,
+    amount: Option<AmountSpec>,
     operations: Vec<SwapOperation>,
-    minimum_receive: Option<Uint128>,
-    to: Option<Addr>,
+    options: SwapOptions,
 ) -> StdResult<Vec<CosmosMsg>> {
-    if operations.len() == 0 {
+    let SwapOptions {
+        minimum_receive,
+        max_slippage,
+        to,
+    } = options;
+    if operations.is_empty() {
         return Err(StdError::GenericErr {
             msg: "Swap operations is empty!!!".to_string(),
         });
@@ -333,12 +708,16 @@ pub fn query_swap_operations_msg(
 
     match offer_asset.clone() {
         AssetInfo::NativeToken { denom } => {
-            let amount = amount.unwrap_or_else(|| {
-                deps.querier
-                    .query_balance(sender.to_string(), denom.clone())
-                    .unwrap()
-                    .amount
-            });
+            let amount = AmountSource::native_balance(amount, denom.clone(), Some(sender))?
+                .resolve(&deps.querier)?;
+            let minimum_receive = query_minimum_receive(
+                deps,
+                &swap_router,
+                amount,
+                &operations,
+                minimum_receive,
+                max_slippage,
+            )?;
             messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: swap_router.to_string(),
                 msg: to_binary(&OraiswapExecuteMsg::ExecuteSwapOperations {
@@ -351,9 +730,17 @@ pub fn query_swap_operations_msg(
         }
 
         AssetInfo::Token { contract_addr } => {
-            let amount = amount.unwrap_or_else(|| {
-                query_token_balance(&deps.querier, contract_addr.clone(), sender.clone()).unwrap()
-            });
+            let amount =
+                AmountSource::token_balance(amount, contract_addr.clone(), Some(sender.clone()))?
+                    .resolve(&deps.querier)?;
+            let minimum_receive = query_minimum_receive(
+                deps,
+                &swap_router,
+                amount,
+                &operations,
+                minimum_receive,
+                max_slippage,
+            )?;
 
             if sender != executor_addr {
                 messages.push(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
@@ -384,7 +771,4 @@ pub fn query_swap_operations_msg(
     Ok(messages)
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
-}
+migrate_entry_point!(MigrateMsg);
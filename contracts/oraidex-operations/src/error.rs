@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use operations_core::OperationsError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,8 +7,11 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
-    #[error("Unauthorized")]
-    Unauthorized {},
+    #[error("{0}")]
+    Operations(#[from] OperationsError),
+
+    #[error("Pair of {asset_infos} is not registered")]
+    PairNotFound { asset_infos: String },
     // Add any other custom errors you like here.
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
 }
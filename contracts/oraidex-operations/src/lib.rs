@@ -2,6 +2,11 @@ pub mod contract;
 mod error;
 pub mod helpers;
 pub mod msg;
+pub mod position;
+pub mod registry;
+pub mod route;
 pub mod state;
+pub mod valuation;
+pub mod zap;
 
 pub use crate::error::ContractError;
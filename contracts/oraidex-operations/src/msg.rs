@@ -1,8 +1,11 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Binary, CosmosMsg, Decimal, Uint128};
+use operations_core::{operation::BatchMessagesResponse, AmountSpec};
 use oraidex::{
-    asset::{Asset, AssetInfo},
+    asset::{Asset, AssetInfo, PairInfo},
+    factory::PairsResponse,
     router::SwapOperation,
+    tax::{MessagesWithTaxResponse, TaxResponse},
 };
 
 #[cw_serde]
@@ -10,6 +13,9 @@ pub struct InstantiateMsg {
     pub owner: Addr,
     pub oraiswap_router: Addr,
     pub oraiswap_staking: Addr,
+    pub oraiswap_factory: Option<Addr>,
+    pub oraiswap_oracle: Option<Addr>,
+    pub require_registered_pair: Option<bool>,
 }
 
 #[cw_serde]
@@ -18,6 +24,16 @@ pub enum ExecuteMsg {
         owner: Option<Addr>,
         oraiswap_router: Option<Addr>,
         oraiswap_staking: Option<Addr>,
+        oraiswap_factory: Option<Addr>,
+        oraiswap_oracle: Option<Addr>,
+        require_registered_pair: Option<bool>,
+    },
+    /// Register the pair info reported by `pair_contract`
+    RegisterPair {
+        pair_contract: Addr,
+    },
+    DeregisterPair {
+        asset_infos: [AssetInfo; 2],
     },
 }
 
@@ -26,8 +42,42 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(Vec<CosmosMsg>)]
     Messages { msg: Binary },
+    #[returns(BatchMessagesResponse)]
+    BatchMessages { msgs: Vec<Binary> },
     #[returns(ConfigResponse)]
     Config {},
+    #[returns(SimulateSwapResponse)]
+    SimulateSwap {
+        offer_amount: Uint128,
+        operations: Vec<SwapOperation>,
+    },
+    /// LP held and bonded by `owner` in the ORAI pair of the `asset_info`
+    /// staking pool, with its pending rewards and underlying assets
+    #[returns(PositionResponse)]
+    Position { owner: Addr, asset_info: AssetInfo },
+    /// Value of the `assets` held by `owner` in `quote_denom`, ORAI by default.
+    /// Only oracle prices count towards `total_value`: cw20 tokens have no
+    /// oracle price and are valued at the spot price of their ORAI pair, which
+    /// can be moved within a transaction, so they are reported in `spot_value`
+    #[returns(ValuationResponse)]
+    Valuation {
+        owner: Addr,
+        assets: Vec<ValuationAsset>,
+        quote_denom: Option<String>,
+    },
+    /// Tax charged on a native transfer of `asset`
+    #[returns(TaxResponse)]
+    Tax { asset: Asset },
+    /// Messages of the operation `msg` with the tax the chain charges on them
+    #[returns(MessagesWithTaxResponse)]
+    MessagesWithTax { msg: Binary },
+    #[returns(PairInfo)]
+    Pair { asset_infos: [AssetInfo; 2] },
+    #[returns(PairsResponse)]
+    Pairs {
+        start_after: Option<[AssetInfo; 2]>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -35,33 +85,95 @@ pub enum Operations {
     SwapOperations {
         executor_addr: Addr,
         sender: Addr,
-        amount: Option<Uint128>,
+        amount: Option<AmountSpec>,
         operations: Vec<SwapOperation>,
         minimum_receive: Option<Uint128>,
+        /// Derive `minimum_receive` from a simulation of the swap at build time
+        max_slippage: Option<Decimal>,
+        to: Option<Addr>,
+        /// Offer a native amount net of the chain tax
+        #[serde(default)]
+        deduct_tax: bool,
+    },
+    /// Swap along the route through registered pairs, of at most `max_hops`
+    /// pairs, that returns the most
+    SwapBestRoute {
+        executor_addr: Addr,
+        sender: Addr,
+        offer_asset: Asset,
+        ask_asset_info: AssetInfo,
+        max_hops: u8,
+        minimum_receive: Option<Uint128>,
+        max_slippage: Option<Decimal>,
         to: Option<Addr>,
+        /// Offer a native amount net of the chain tax
+        #[serde(default)]
+        deduct_tax: bool,
     },
+    /// cw20 legs are pulled from `sender` when it is set and differs from
+    /// `executor_addr`, otherwise the executor provides from its own balance
     ProvideLiquidity {
+        executor_addr: Option<Addr>,
+        sender: Option<Addr>,
         pair_contract: Addr,
         assets: [Asset; 2],
         slippage_tolerance: Option<Decimal>,
         receiver: Option<Addr>,
+        /// Provide native legs net of the chain tax
+        #[serde(default)]
+        deduct_tax: bool,
+    },
+    /// Swap part of `offer_asset` into the other asset of the pair and provide
+    /// both as liquidity, optionally bonding the minted LP. The swap output and
+    /// the bonded LP are the estimates reduced by `slippage_tolerance`, 0.5%
+    /// if unset
+    ZapIn {
+        executor_addr: Addr,
+        pair_contract: Addr,
+        offer_asset: Asset,
+        slippage_tolerance: Option<Decimal>,
+        bond: bool,
+        /// Swap and provide a native offer net of the chain tax
+        #[serde(default)]
+        deduct_tax: bool,
     },
     WithdrawLiquidity {
         sender: Option<Addr>,
         pair_contract: Addr,
         lp_token: Addr,
-        amount: Option<Uint128>,
+        amount: Option<AmountSpec>,
+    },
+    /// Withdraw liquidity, optionally unbonding it first, and swap the other
+    /// asset of the pair into `target_asset_info`. The swapped amount is the
+    /// estimated refund reduced by `slippage_tolerance`, 0.5% if unset
+    ZapOut {
+        executor_addr: Addr,
+        pair_contract: Addr,
+        lp_token: Addr,
+        amount: Option<AmountSpec>,
+        target_asset_info: AssetInfo,
+        minimum_receive: Option<Uint128>,
+        unbond_first: bool,
+        slippage_tolerance: Option<Decimal>,
+    },
+    /// Withdraw the pending rewards of a staking pool, swap half into the other
+    /// asset of the pair, provide liquidity and re-bond the LP
+    Compound {
+        executor_addr: Addr,
+        asset_info: AssetInfo,
+        pair_contract: Addr,
+        slippage_tolerance: Option<Decimal>,
     },
     Bond {
         sender: Option<Addr>,
         lp_token: Addr,
         asset_info: AssetInfo,
-        amount: Option<Uint128>,
+        amount: Option<AmountSpec>,
     },
     Unbond {
         sender: Option<Addr>,
         asset_info: AssetInfo,
-        amount: Option<Uint128>,
+        amount: Option<AmountSpec>,
     },
     /// Withdraw pending rewards
     Withdraw {
@@ -75,6 +187,63 @@ pub struct ConfigResponse {
     pub owner: Addr,
     pub oraiswap_router: Addr,
     pub oraiswap_staking: Addr,
+    pub oraiswap_factory: Option<Addr>,
+    pub oraiswap_oracle: Option<Addr>,
+    pub require_registered_pair: bool,
+}
+
+#[cw_serde]
+pub struct SimulateSwapResponse {
+    /// Expected amount received after each hop
+    pub amounts: Vec<Uint128>,
+    pub return_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct PositionResponse {
+    pub pair_contract: Addr,
+    pub lp_token: Addr,
+    pub lp_balance: Uint128,
+    pub bond_amount: Uint128,
+    pub pending_rewards: Vec<Asset>,
+    /// Pool assets redeemable for the held and bonded LP together
+    pub underlying: [Asset; 2],
+}
+
+#[cw_serde]
+pub enum ValuationAsset {
+    /// Native or cw20 balance
+    Balance { asset_info: AssetInfo },
+    /// LP held and bonded in the ORAI pair of the `asset_info` staking pool,
+    /// valued by its underlying assets
+    Liquidity { asset_info: AssetInfo },
+}
+
+#[cw_serde]
+pub enum PriceSource {
+    /// Oracle exchange rates only
+    Oracle,
+    /// Spot pool reserves, manipulable within a transaction
+    Spot,
+}
+
+#[cw_serde]
+pub struct ValuationItem {
+    pub asset: ValuationAsset,
+    /// Balance, or LP held and bonded for `Liquidity`
+    pub amount: Uint128,
+    pub value: Uint128,
+    pub price_source: PriceSource,
+}
+
+#[cw_serde]
+pub struct ValuationResponse {
+    pub quote_denom: String,
+    pub items: Vec<ValuationItem>,
+    /// Sum of the oracle priced items
+    pub total_value: Uint128,
+    /// Sum of the spot priced items, not safe to act on
+    pub spot_value: Uint128,
 }
 
 #[cw_serde]
@@ -0,0 +1,66 @@
+use cosmwasm_std::{Addr, Deps, StdError, StdResult, Uint128};
+use oraidex::{
+    asset::{Asset, AssetInfo, ORAI_DENOM},
+    querier::{query_pair_info, query_reward_info, query_supply, query_token_balance},
+};
+
+use crate::{msg::PositionResponse, state::CONFIG, zap::query_pending_rewards};
+
+/// Position of `owner` in the ORAI pair of the `asset_info` staking pool.
+pub fn query_position(
+    deps: Deps,
+    owner: Addr,
+    asset_info: AssetInfo,
+) -> StdResult<PositionResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let factory = config
+        .oraiswap_factory
+        .ok_or_else(|| StdError::generic_err("Oraiswap factory is not configured"))?;
+    let oraiswap_staking = deps.api.addr_humanize(&config.oraiswap_staking)?;
+    let pair_info = query_pair_info(
+        &deps.querier,
+        deps.api.addr_humanize(&factory)?,
+        &[
+            AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            },
+            asset_info.clone(),
+        ],
+    )?;
+
+    let lp_balance = query_token_balance(
+        &deps.querier,
+        pair_info.liquidity_token.clone(),
+        owner.clone(),
+    )?;
+    let (bond_amount, pending_rewards) =
+        match query_reward_info(&deps.querier, oraiswap_staking.clone(), owner, asset_info)? {
+            Some(reward_info) => (
+                reward_info.bond_amount,
+                query_pending_rewards(deps, oraiswap_staking, reward_info)?,
+            ),
+            None => (Uint128::zero(), vec![]),
+        };
+
+    // the bonded LP is redeemable like the held one
+    let share = lp_balance + bond_amount;
+    let total_share = query_supply(&deps.querier, pair_info.liquidity_token.clone())?;
+    let pools = pair_info.query_pools(&deps.querier, pair_info.contract_addr.clone())?;
+    let underlying = pools.map(|pool| Asset {
+        amount: if total_share.is_zero() {
+            Uint128::zero()
+        } else {
+            pool.amount.multiply_ratio(share, total_share)
+        },
+        info: pool.info,
+    });
+
+    Ok(PositionResponse {
+        pair_contract: pair_info.contract_addr,
+        lp_token: pair_info.liquidity_token,
+        lp_balance,
+        bond_amount,
+        pending_rewards,
+        underlying,
+    })
+}
@@ -0,0 +1,302 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, MessageInfo, Order, Response, StdError, StdResult};
+use cw_storage_plus::Bound;
+use operations_core::ownership::assert_owner;
+use oraidex::{
+    asset::{pair_key, AssetInfo, PairInfo, ORAI_DENOM},
+    factory::PairsResponse,
+    querier::query_pair_info_from_pair,
+};
+
+use crate::{
+    error::ContractError,
+    state::{CONFIG, PAIRS},
+};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+pub fn execute_register_pair(
+    deps: DepsMut,
+    info: MessageInfo,
+    pair_contract: Addr,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_owner(deps.api, &config, &info.sender)?;
+
+    let pair_info = query_pair_info_from_pair(&deps.querier, pair_contract.clone())?;
+    if pair_info.contract_addr != pair_contract {
+        return Err(StdError::generic_err(format!(
+            "{} reports pair contract {}",
+            pair_contract, pair_info.contract_addr
+        ))
+        .into());
+    }
+    let pair_raw = pair_info.to_raw(deps.api)?;
+    PAIRS.save(deps.storage, &pair_key(&pair_raw.asset_infos), &pair_raw)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "register_pair"),
+        ("pair_contract", pair_contract.as_str()),
+        ("liquidity_token", pair_info.liquidity_token.as_str()),
+    ]))
+}
+
+pub fn execute_deregister_pair(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_infos: [AssetInfo; 2],
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_owner(deps.api, &config, &info.sender)?;
+
+    let key = raw_pair_key(deps.as_ref(), &asset_infos)?;
+    if !PAIRS.has(deps.storage, &key) {
+        return Err(ContractError::PairNotFound {
+            asset_infos: format!("{}-{}", asset_infos[0], asset_infos[1]),
+        });
+    }
+    PAIRS.remove(deps.storage, &key);
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "deregister_pair"),
+        (
+            "asset_infos",
+            &format!("{}-{}", asset_infos[0], asset_infos[1]),
+        ),
+    ]))
+}
+
+/// Fails when the registry is enforced and `pair_contract`, or `lp_token` when
+/// given, does not belong to a registered pair.
+pub fn assert_registered_pair(
+    deps: Deps,
+    pair_contract: &Addr,
+    lp_token: Option<&Addr>,
+) -> StdResult<()> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.require_registered_pair {
+        return Ok(());
+    }
+
+    // the pair only tells which entry to compare against
+    let pair_info = query_pair_info_from_pair(&deps.querier, pair_contract.clone())?;
+    let registered = PAIRS
+        .may_load(deps.storage, &raw_pair_key(deps, &pair_info.asset_infos)?)?
+        .map(|pair_raw| pair_raw.to_normal(deps.api))
+        .transpose()?;
+    let matches = match (registered, lp_token) {
+        (Some(registered), Some(lp_token)) => {
+            registered.contract_addr == *pair_contract && registered.liquidity_token == *lp_token
+        }
+        (Some(registered), None) => registered.contract_addr == *pair_contract,
+        (None, _) => false,
+    };
+    if !matches {
+        return Err(StdError::generic_err(format!(
+            "Pair {} is not registered",
+            pair_contract
+        )));
+    }
+    Ok(())
+}
+
+/// Fails when the registry is enforced and the ORAI pair of the `asset_info`
+/// staking pool, or its `lp_token` when given, is not registered.
+pub fn assert_registered_staking_pool(
+    deps: Deps,
+    asset_info: &AssetInfo,
+    lp_token: Option<&Addr>,
+) -> StdResult<()> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.require_registered_pair {
+        return Ok(());
+    }
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: ORAI_DENOM.to_string(),
+        },
+        asset_info.clone(),
+    ];
+    let registered = PAIRS
+        .may_load(deps.storage, &raw_pair_key(deps, &asset_infos)?)?
+        .map(|pair_raw| pair_raw.to_normal(deps.api))
+        .transpose()?;
+    let matches = match (registered, lp_token) {
+        (Some(registered), Some(lp_token)) => registered.liquidity_token == *lp_token,
+        (Some(_), None) => true,
+        (None, _) => false,
+    };
+    if !matches {
+        return Err(StdError::generic_err(format!(
+            "Staking pool {} is not registered",
+            asset_info
+        )));
+    }
+    Ok(())
+}
+
+pub fn query_pair(deps: Deps, asset_infos: [AssetInfo; 2]) -> StdResult<PairInfo> {
+    PAIRS
+        .may_load(deps.storage, &raw_pair_key(deps, &asset_infos)?)?
+        .ok_or_else(|| StdError::not_found(format!("Pair {}-{}", asset_infos[0], asset_infos[1])))?
+        .to_normal(deps.api)
+}
+
+pub fn query_pairs(
+    deps: Deps,
+    start_after: Option<[AssetInfo; 2]>,
+    limit: Option<u32>,
+) -> StdResult<PairsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|asset_infos| raw_pair_key(deps, &asset_infos))
+        .transpose()?;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let pairs = PAIRS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.and_then(|(_, pair_raw)| pair_raw.to_normal(deps.api)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PairsResponse { pairs })
+}
+
+/// Every registered pair, the set `SwapBestRoute` searches.
+pub fn query_registered_pairs(deps: Deps) -> StdResult<Vec<PairInfo>> {
+    PAIRS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.and_then(|(_, pair_raw)| pair_raw.to_normal(deps.api)))
+        .collect()
+}
+
+fn raw_pair_key(deps: Deps, asset_infos: &[AssetInfo; 2]) -> StdResult<Vec<u8>> {
+    Ok(pair_key(&[
+        asset_infos[0].to_raw(deps.api)?,
+        asset_infos[1].to_raw(deps.api)?,
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{
+        from_slice, to_binary, Api, ContractResult, OwnedDeps, SystemResult, WasmQuery,
+    };
+    use oraidex::pair::PairResponse;
+
+    use crate::state::Config;
+
+    fn native(denom: &str) -> AssetInfo {
+        AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        }
+    }
+
+    fn pair(denom: &str) -> PairInfo {
+        PairInfo {
+            asset_infos: [native(ORAI_DENOM), native(denom)],
+            contract_addr: Addr::unchecked(format!("pair{}", denom)),
+            liquidity_token: Addr::unchecked(format!("lp{}", denom)),
+            oracle_addr: Addr::unchecked("oracle"),
+            commission_rate: "0.003".to_string(),
+        }
+    }
+
+    /// `usdt` is registered, `atom` only exists on chain.
+    fn setup(require_registered_pair: bool) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        let config = Config {
+            owner: deps.api.addr_canonicalize("owner").unwrap(),
+            oraiswap_router: deps.api.addr_canonicalize("router").unwrap(),
+            oraiswap_staking: deps.api.addr_canonicalize("staking").unwrap(),
+            oraiswap_factory: None,
+            oraiswap_oracle: None,
+            require_registered_pair,
+        };
+        CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+        let pair_raw = pair("usdt").to_raw(deps.as_ref().api).unwrap();
+        PAIRS
+            .save(
+                deps.as_mut().storage,
+                &pair_key(&pair_raw.asset_infos),
+                &pair_raw,
+            )
+            .unwrap();
+
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                let _: oraidex::pair::PairQueryMsg = from_slice(msg).unwrap();
+                let info = pair(contract_addr.trim_start_matches("pair"));
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&PairResponse { info }).unwrap(),
+                ))
+            }
+            _ => panic!("unexpected query"),
+        });
+        deps
+    }
+
+    #[test]
+    fn registered_pairs_pass() {
+        let deps = setup(true);
+        let usdt = pair("usdt");
+        assert_registered_pair(deps.as_ref(), &usdt.contract_addr, None).unwrap();
+        assert_registered_pair(
+            deps.as_ref(),
+            &usdt.contract_addr,
+            Some(&usdt.liquidity_token),
+        )
+        .unwrap();
+        assert_registered_staking_pool(deps.as_ref(), &native("usdt"), Some(&usdt.liquidity_token))
+            .unwrap();
+        assert_registered_staking_pool(deps.as_ref(), &native("usdt"), None).unwrap();
+    }
+
+    #[test]
+    fn unregistered_pairs_fail() {
+        let deps = setup(true);
+        let usdt = pair("usdt");
+        let atom = pair("atom");
+        assert!(assert_registered_pair(deps.as_ref(), &atom.contract_addr, None).is_err());
+        assert!(assert_registered_pair(
+            deps.as_ref(),
+            &usdt.contract_addr,
+            Some(&atom.liquidity_token)
+        )
+        .is_err());
+        assert!(assert_registered_staking_pool(deps.as_ref(), &native("atom"), None).is_err());
+        assert!(assert_registered_staking_pool(
+            deps.as_ref(),
+            &native("usdt"),
+            Some(&atom.liquidity_token)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn registry_not_enforced() {
+        let deps = setup(false);
+        let atom = pair("atom");
+        assert_registered_pair(deps.as_ref(), &atom.contract_addr, None).unwrap();
+        assert_registered_staking_pool(deps.as_ref(), &native("atom"), Some(&atom.liquidity_token))
+            .unwrap();
+    }
+
+    #[test]
+    fn registered_pairs_are_listed() {
+        let deps = setup(true);
+        assert_eq!(
+            query_registered_pairs(deps.as_ref()).unwrap(),
+            vec![pair("usdt")]
+        );
+        assert_eq!(
+            query_pair(deps.as_ref(), [native("usdt"), native(ORAI_DENOM)]).unwrap(),
+            pair("usdt")
+        );
+        assert!(query_pair(deps.as_ref(), [native(ORAI_DENOM), native("atom")]).is_err());
+    }
+}
@@ -0,0 +1,240 @@
+use cosmwasm_std::{Addr, CosmosMsg, Decimal, Deps, Env, StdError, StdResult, Uint128};
+use operations_core::AmountSpec;
+use oraidex::{
+    asset::{Asset, AssetInfo, PairInfo},
+    querier::simulate_swap_operations,
+    router::SwapOperation,
+};
+
+use crate::{
+    contract::{query_swap_operations_msg, SwapOptions},
+    registry::query_registered_pairs,
+    state::CONFIG,
+};
+
+/// Upper bound for `max_hops`
+const MAX_HOPS: u8 = 3;
+/// Upper bound for the candidate routes, every one costs a simulation
+const MAX_ROUTES: usize = 8;
+
+#[allow(clippy::too_many_arguments)]
+pub fn query_swap_best_route_msg(
+    deps: Deps,
+    env: Env,
+    executor_addr: Addr,
+    sender: Addr,
+    offer_asset: Asset,
+    ask_asset_info: AssetInfo,
+    max_hops: u8,
+    minimum_receive: Option<Uint128>,
+    max_slippage: Option<Decimal>,
+    to: Option<Addr>,
+) -> StdResult<Vec<CosmosMsg>> {
+    if max_hops == 0 || max_hops > MAX_HOPS {
+        return Err(StdError::generic_err(format!(
+            "max_hops must be between 1 and {}",
+            MAX_HOPS
+        )));
+    }
+    if offer_asset.info == ask_asset_info {
+        return Err(StdError::generic_err("Offer and ask asset are the same"));
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let swap_router = deps.api.addr_humanize(&config.oraiswap_router)?;
+    let pairs = query_registered_pairs(deps)?;
+
+    let routes = find_routes(&pairs, &offer_asset.info, &ask_asset_info, max_hops);
+    if routes.is_empty() {
+        return Err(StdError::generic_err(format!(
+            "No route from {} to {}",
+            offer_asset.info, ask_asset_info
+        )));
+    }
+
+    let mut best: Option<(Uint128, Vec<SwapOperation>)> = None;
+    let mut last_error: Option<StdError> = None;
+    for route in routes {
+        // a route through an empty pool fails to simulate, it is not a candidate
+        let amount = match simulate_swap_operations(
+            &deps.querier,
+            swap_router.clone(),
+            offer_asset.amount,
+            route.clone(),
+        ) {
+            Ok(amount) => amount,
+            Err(err) => {
+                last_error = Some(err);
+                continue;
+            }
+        };
+        let improves = match &best {
+            Some((best_amount, _)) => amount > *best_amount,
+            None => true,
+        };
+        if improves {
+            best = Some((amount, route));
+        }
+    }
+
+    let (_, operations) = best.ok_or_else(|| {
+        StdError::generic_err(format!(
+            "No route from {} to {} simulates: {}",
+            offer_asset.info,
+            ask_asset_info,
+            last_error.map(|err| err.to_string()).unwrap_or_default()
+        ))
+    })?;
+
+    query_swap_operations_msg(
+        deps,
+        env,
+        executor_addr,
+        sender,
+        Some(AmountSpec::Exact(offer_asset.amount)),
+        operations,
+        SwapOptions {
+            minimum_receive,
+            max_slippage,
+            to,
+        },
+    )
+}
+
+/// Paths of at most `max_hops` pairs from `offer` to `ask` that do not visit
+/// an asset twice, shortest first and at most `MAX_ROUTES` of them.
+fn find_routes(
+    pairs: &[PairInfo],
+    offer: &AssetInfo,
+    ask: &AssetInfo,
+    max_hops: u8,
+) -> Vec<Vec<SwapOperation>> {
+    let mut routes: Vec<Vec<SwapOperation>> = vec![];
+    for hops in 1..=max_hops as usize {
+        let mut visited: Vec<AssetInfo> = vec![offer.clone()];
+        let mut route: Vec<SwapOperation> = vec![];
+        walk_routes(pairs, ask, hops, &mut visited, &mut route, &mut routes);
+        if routes.len() >= MAX_ROUTES {
+            break;
+        }
+    }
+    routes
+}
+
+/// Extend `route` to routes of exactly `hops` pairs ending in `ask`.
+fn walk_routes(
+    pairs: &[PairInfo],
+    ask: &AssetInfo,
+    hops: usize,
+    visited: &mut Vec<AssetInfo>,
+    route: &mut Vec<SwapOperation>,
+    routes: &mut Vec<Vec<SwapOperation>>,
+) {
+    let current = visited[visited.len() - 1].clone();
+    for pair in pairs {
+        if routes.len() >= MAX_ROUTES {
+            return;
+        }
+        let next = if pair.asset_infos[0] == current {
+            &pair.asset_infos[1]
+        } else if pair.asset_infos[1] == current {
+            &pair.asset_infos[0]
+        } else {
+            continue;
+        };
+        if visited.contains(next) {
+            continue;
+        }
+
+        route.push(SwapOperation::OraiSwap {
+            offer_asset_info: current.clone(),
+            ask_asset_info: next.clone(),
+        });
+        if route.len() == hops {
+            if next == ask {
+                routes.push(route.clone());
+            }
+        } else if next != ask {
+            visited.push(next.clone());
+            walk_routes(pairs, ask, hops, visited, route, routes);
+            visited.pop();
+        }
+        route.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn native(denom: &str) -> AssetInfo {
+        AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        }
+    }
+
+    fn pair(asset_infos: [AssetInfo; 2]) -> PairInfo {
+        let name = format!("{}-{}", asset_infos[0], asset_infos[1]);
+        PairInfo {
+            asset_infos,
+            contract_addr: Addr::unchecked(format!("pair-{}", name)),
+            liquidity_token: Addr::unchecked(format!("lp-{}", name)),
+            oracle_addr: Addr::unchecked("oracle"),
+            commission_rate: "0.003".to_string(),
+        }
+    }
+
+    fn hops(route: &[SwapOperation]) -> Vec<String> {
+        route
+            .iter()
+            .map(|operation| operation.get_target_asset_info().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn routes_shortest_first() {
+        let pairs = vec![
+            pair([native("a"), native("b")]),
+            pair([native("b"), native("c")]),
+            pair([native("a"), native("c")]),
+            pair([native("c"), native("d")]),
+        ];
+
+        let routes = find_routes(&pairs, &native("a"), &native("c"), 3);
+        let routes: Vec<Vec<String>> = routes.iter().map(|route| hops(route)).collect();
+        assert_eq!(routes, vec![vec!["c"], vec!["b", "c"]]);
+
+        // the direct pair only counts as a one hop route
+        let routes = find_routes(&pairs, &native("a"), &native("c"), 1);
+        assert_eq!(routes.len(), 1);
+    }
+
+    #[test]
+    fn routes_respect_max_hops_and_cycles() {
+        let pairs = vec![
+            pair([native("a"), native("b")]),
+            pair([native("b"), native("c")]),
+            pair([native("c"), native("d")]),
+        ];
+
+        assert!(find_routes(&pairs, &native("a"), &native("d"), 2).is_empty());
+        let routes = find_routes(&pairs, &native("a"), &native("d"), 3);
+        assert_eq!(hops(&routes[0]), vec!["b", "c", "d"]);
+        assert!(find_routes(&pairs, &native("a"), &native("e"), 3).is_empty());
+    }
+
+    #[test]
+    fn routes_are_capped() {
+        // every intermediate x offers a two hop route from a to z
+        let mut pairs = vec![];
+        for index in 0..2 * MAX_ROUTES {
+            let intermediate = native(&format!("x{}", index));
+            pairs.push(pair([native("a"), intermediate.clone()]));
+            pairs.push(pair([intermediate, native("z")]));
+        }
+
+        let routes = find_routes(&pairs, &native("a"), &native("z"), 3);
+        assert_eq!(routes.len(), MAX_ROUTES);
+        assert!(routes.iter().all(|route| route.len() == 2));
+    }
+}
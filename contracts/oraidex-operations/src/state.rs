@@ -1,6 +1,8 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::CanonicalAddr;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
+use operations_core::impl_owned_config;
+use oraidex::asset::PairInfoRaw;
 
 // put the length bytes at the first for compatibility with legacy singleton store
 pub const CONFIG: Item<Config> = Item::new("\u{0}\u{6}config");
@@ -10,4 +12,14 @@ pub struct Config {
     pub owner: CanonicalAddr,
     pub oraiswap_router: CanonicalAddr,
     pub oraiswap_staking: CanonicalAddr,
+    pub oraiswap_factory: Option<CanonicalAddr>,
+    pub oraiswap_oracle: Option<CanonicalAddr>,
+    /// Reject operations on pairs missing from `PAIRS`
+    #[serde(default)]
+    pub require_registered_pair: bool,
 }
+
+/// Owner-approved pairs keyed by `pair_key` of their asset infos
+pub const PAIRS: Map<&[u8], PairInfoRaw> = Map::new("pairs");
+
+impl_owned_config!(Config);
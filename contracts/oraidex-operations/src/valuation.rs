@@ -0,0 +1,203 @@
+use cosmwasm_std::{Addr, Decimal, Decimal256, Deps, Isqrt, StdError, StdResult, Uint128, Uint256};
+use oraidex::{
+    asset::{Asset, AssetInfo, ORAI_DENOM},
+    oracle::OracleContract,
+    querier::{query_pair_info, query_token_balance},
+};
+
+use crate::{
+    msg::{PriceSource, ValuationAsset, ValuationItem, ValuationResponse},
+    position::query_position,
+    state::CONFIG,
+};
+
+pub fn query_valuation(
+    deps: Deps,
+    owner: Addr,
+    assets: Vec<ValuationAsset>,
+    quote_denom: Option<String>,
+) -> StdResult<ValuationResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let oracle = OracleContract::from_config(deps.api, config.oraiswap_oracle.as_ref())?;
+    let quote_denom = quote_denom.unwrap_or_else(|| ORAI_DENOM.to_string());
+
+    let mut items: Vec<ValuationItem> = vec![];
+    let mut total_value = Uint128::zero();
+    let mut spot_value = Uint128::zero();
+    for asset in assets {
+        let (amount, value, price_source) = match &asset {
+            ValuationAsset::Balance { asset_info } => {
+                let amount = query_balance(deps, &owner, asset_info)?;
+                let (value, price_source) = query_value(
+                    deps,
+                    &oracle,
+                    &quote_denom,
+                    &Asset {
+                        info: asset_info.clone(),
+                        amount,
+                    },
+                )?;
+                (amount, value, price_source)
+            }
+            ValuationAsset::Liquidity { asset_info } => {
+                let position = query_position(deps, owner.clone(), asset_info.clone())?;
+                let (value, price_source) =
+                    query_liquidity_value(deps, &oracle, &quote_denom, &position.underlying)?;
+                (
+                    position.lp_balance.checked_add(position.bond_amount)?,
+                    value,
+                    price_source,
+                )
+            }
+        };
+        match price_source {
+            PriceSource::Oracle => total_value = total_value.checked_add(value)?,
+            PriceSource::Spot => spot_value = spot_value.checked_add(value)?,
+        }
+        items.push(ValuationItem {
+            asset,
+            amount,
+            value,
+            price_source,
+        });
+    }
+
+    Ok(ValuationResponse {
+        quote_denom,
+        items,
+        total_value,
+        spot_value,
+    })
+}
+
+fn query_balance(deps: Deps, owner: &Addr, asset_info: &AssetInfo) -> StdResult<Uint128> {
+    match asset_info {
+        AssetInfo::NativeToken { denom } => Ok(deps.querier.query_balance(owner, denom)?.amount),
+        AssetInfo::Token { contract_addr } => {
+            query_token_balance(&deps.querier, contract_addr.clone(), owner.clone())
+        }
+    }
+}
+
+/// Value of an LP share redeemable for `underlying`. With oracle prices for
+/// both legs this is the fair value `2 sqrt(v0 v1)`, which a swap moving the
+/// reserves along the constant product curve cannot inflate.
+fn query_liquidity_value(
+    deps: Deps,
+    oracle: &OracleContract,
+    quote_denom: &str,
+    underlying: &[Asset; 2],
+) -> StdResult<(Uint128, PriceSource)> {
+    let (value_0, source_0) = query_value(deps, oracle, quote_denom, &underlying[0])?;
+    let (value_1, source_1) = query_value(deps, oracle, quote_denom, &underlying[1])?;
+    if source_0 == PriceSource::Spot || source_1 == PriceSource::Spot {
+        return Ok((value_0.checked_add(value_1)?, PriceSource::Spot));
+    }
+    Ok((fair_liquidity_value(value_0, value_1)?, PriceSource::Oracle))
+}
+
+fn fair_liquidity_value(value_0: Uint128, value_1: Uint128) -> StdResult<Uint128> {
+    let product = Uint256::from(value_0) * Uint256::from(value_1);
+    Ok(Uint128::try_from(product.isqrt())?.checked_mul(Uint128::new(2))?)
+}
+
+fn query_value(
+    deps: Deps,
+    oracle: &OracleContract,
+    quote_denom: &str,
+    asset: &Asset,
+) -> StdResult<(Uint128, PriceSource)> {
+    if asset.amount.is_zero() {
+        let price_source = match asset.info {
+            AssetInfo::NativeToken { .. } => PriceSource::Oracle,
+            AssetInfo::Token { .. } => PriceSource::Spot,
+        };
+        return Ok((Uint128::zero(), price_source));
+    }
+    let (price, price_source) = query_price(deps, oracle, quote_denom, &asset.info)?;
+    let value = Uint128::try_from(Uint256::from(asset.amount) * Decimal256::from(price))?;
+    Ok((value, price_source))
+}
+
+/// Price of one unit of `asset_info` in `quote_denom`. The oracle only quotes
+/// native denoms, cw20 tokens go through the spot price of their ORAI pair.
+fn query_price(
+    deps: Deps,
+    oracle: &OracleContract,
+    quote_denom: &str,
+    asset_info: &AssetInfo,
+) -> StdResult<(Decimal, PriceSource)> {
+    match asset_info {
+        AssetInfo::NativeToken { denom } if denom == quote_denom => {
+            Ok((Decimal::one(), PriceSource::Oracle))
+        }
+        AssetInfo::NativeToken { denom } => Ok((
+            oracle
+                .query_exchange_rate(&deps.querier, denom.as_str(), quote_denom)?
+                .item
+                .exchange_rate,
+            PriceSource::Oracle,
+        )),
+        AssetInfo::Token { .. } => {
+            let config = CONFIG.load(deps.storage)?;
+            let factory = config
+                .oraiswap_factory
+                .ok_or_else(|| StdError::generic_err("Oraiswap factory is not configured"))?;
+            let orai = AssetInfo::NativeToken {
+                denom: ORAI_DENOM.to_string(),
+            };
+            let pair_info = query_pair_info(
+                &deps.querier,
+                deps.api.addr_humanize(&factory)?,
+                &[orai.clone(), asset_info.clone()],
+            )?;
+            let pools = pair_info.query_pools(&deps.querier, pair_info.contract_addr.clone())?;
+            let (orai_pool, token_pool) = if pools[0].info == orai {
+                (pools[0].amount, pools[1].amount)
+            } else {
+                (pools[1].amount, pools[0].amount)
+            };
+            if token_pool.is_zero() {
+                return Err(StdError::generic_err(format!(
+                    "Pool of {} is empty",
+                    asset_info
+                )));
+            }
+
+            let (orai_price, _) = query_price(deps, oracle, quote_denom, &orai)?;
+            Ok((
+                Decimal::from_ratio(orai_pool, token_pool) * orai_price,
+                PriceSource::Spot,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fair_value_of_balanced_liquidity() {
+        // a balanced pool is worth the sum of its legs
+        assert_eq!(
+            fair_liquidity_value(Uint128::new(500), Uint128::new(500)).unwrap(),
+            Uint128::new(1000)
+        );
+        // a swap skewing the reserves along the curve inflates the sum of
+        // the legs, not the fair value
+        assert_eq!(
+            fair_liquidity_value(Uint128::new(2000), Uint128::new(125)).unwrap(),
+            Uint128::new(1000)
+        );
+        assert_eq!(
+            fair_liquidity_value(Uint128::zero(), Uint128::new(500)).unwrap(),
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn fair_value_overflow_is_an_error() {
+        assert!(fair_liquidity_value(Uint128::MAX, Uint128::MAX).is_err());
+    }
+}
@@ -0,0 +1,609 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{
+    Addr, CosmosMsg, Decimal, Deps, Env, Isqrt, StdError, StdResult, Uint128, Uint512,
+};
+use operations_core::{AmountSource, AmountSpec};
+use oraidex::{
+    asset::{Asset, AssetInfo, PairInfo, ORAI_DENOM},
+    querier::{
+        query_pair_info_from_pair, query_reward_info, query_rewards_per_sec, query_supply,
+        simulate_swap_operations,
+    },
+    router::SwapOperation,
+    staking::RewardInfoResponseItem,
+};
+
+use crate::{
+    contract::{
+        query_amount_after_tax, query_bond_msg, query_bonded_amount, query_provide_liquidity_msg,
+        query_swap_operations_msg, query_unbond_msg, query_withdraw_liquidity_msg,
+        query_withdraw_msg, SwapOptions,
+    },
+    state::CONFIG,
+};
+
+/// Tolerance applied to build time estimates when the caller sets none
+const DEFAULT_SLIPPAGE_TOLERANCE_PERMILLE: u64 = 5;
+
+/// With `deduct_tax` the tax on a native offer is deducted from the swapped
+/// and the provided part each, as both are transferred separately.
+#[allow(clippy::too_many_arguments)]
+pub fn query_zap_in_msg(
+    deps: Deps,
+    env: Env,
+    executor_addr: Addr,
+    pair_contract: Addr,
+    offer_asset: Asset,
+    slippage_tolerance: Option<Decimal>,
+    bond: bool,
+    deduct_tax: bool,
+) -> StdResult<Vec<CosmosMsg>> {
+    if offer_asset.amount.is_zero() {
+        return Err(StdError::generic_err("Offer amount is zero"));
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let swap_router = deps.api.addr_humanize(&config.oraiswap_router)?;
+    let pair_info = query_pair_info_from_pair(&deps.querier, pair_contract.clone())?;
+    let pools = pair_info.query_pools(&deps.querier, pair_contract.clone())?;
+
+    let (offer_pool, ask_pool) = if pools[0].info == offer_asset.info {
+        (pools[0].amount, &pools[1])
+    } else if pools[1].info == offer_asset.info {
+        (pools[1].amount, &pools[0])
+    } else {
+        return Err(StdError::generic_err(format!(
+            "{} is not an asset of pair {}",
+            offer_asset.info, pair_contract
+        )));
+    };
+    let ask_asset_info = ask_pool.info.clone();
+
+    let commission_rate = Decimal::from_str(&pair_info.commission_rate)?;
+    let swap_amount = optimal_swap_amount(offer_asset.amount, offer_pool, commission_rate)?;
+    let provide_amount = query_amount_after_tax(
+        deps,
+        &offer_asset.info,
+        offer_asset.amount - swap_amount,
+        deduct_tax,
+    )?;
+    let swap_amount = query_amount_after_tax(deps, &offer_asset.info, swap_amount, deduct_tax)?;
+    let operations = vec![SwapOperation::OraiSwap {
+        offer_asset_info: offer_asset.info.clone(),
+        ask_asset_info: ask_asset_info.clone(),
+    }];
+    let return_amount =
+        simulate_swap_operations(&deps.querier, swap_router, swap_amount, operations.clone())?;
+    // only the guaranteed part of the swap output is provided, with the offer
+    // leg matched to it so nothing is left over in the pool
+    let minimum_receive = guaranteed_return(return_amount, slippage_tolerance)?;
+    let reserves = [
+        offer_pool + swap_amount,
+        ask_pool.amount.checked_sub(return_amount)?,
+    ];
+    let deposits = balanced_deposits([provide_amount, minimum_receive], reserves)?;
+
+    let mut messages = query_swap_operations_msg(
+        deps,
+        env.clone(),
+        executor_addr.clone(),
+        executor_addr.clone(),
+        Some(AmountSpec::Exact(swap_amount)),
+        operations,
+        SwapOptions {
+            minimum_receive: Some(minimum_receive),
+            ..SwapOptions::default()
+        },
+    )?;
+
+    let assets = [
+        Asset {
+            info: offer_asset.info.clone(),
+            amount: deposits[0],
+        },
+        Asset {
+            info: ask_asset_info,
+            amount: deposits[1],
+        },
+    ];
+
+    // the minted LP is not known yet, bond the guaranteed part of the share
+    // estimated from the pool state right before the liquidity is provided
+    let bond_messages = if bond {
+        let share = estimate_share(&deps, &pair_info, deposits, reserves)?;
+        query_bond_msg(
+            deps,
+            env.clone(),
+            None,
+            pair_info.liquidity_token.clone(),
+            staking_asset_info(&pair_info),
+            Some(AmountSpec::Exact(guaranteed_return(
+                share,
+                slippage_tolerance,
+            )?)),
+        )?
+    } else {
+        vec![]
+    };
+
+    messages.extend(query_provide_liquidity_msg(
+        deps,
+        env,
+        None,
+        None,
+        pair_contract,
+        assets,
+        slippage_tolerance,
+        None,
+    )?);
+    messages.extend(bond_messages);
+
+    Ok(messages)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn query_zap_out_msg(
+    deps: Deps,
+    env: Env,
+    executor_addr: Addr,
+    pair_contract: Addr,
+    lp_token: Addr,
+    amount: Option<AmountSpec>,
+    target_asset_info: AssetInfo,
+    minimum_receive: Option<Uint128>,
+    unbond_first: bool,
+    slippage_tolerance: Option<Decimal>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let pair_info = query_pair_info_from_pair(&deps.querier, pair_contract.clone())?;
+    if pair_info.liquidity_token != lp_token {
+        return Err(StdError::generic_err(format!(
+            "{} is not the liquidity token of pair {}",
+            lp_token, pair_contract
+        )));
+    }
+    let pools = pair_info.query_pools(&deps.querier, pair_contract.clone())?;
+    let (target_pool, other_pool) = if pools[0].info == target_asset_info {
+        (pools[0].amount, &pools[1])
+    } else if pools[1].info == target_asset_info {
+        (pools[1].amount, &pools[0])
+    } else {
+        return Err(StdError::generic_err(format!(
+            "{} is not an asset of pair {}",
+            target_asset_info, pair_contract
+        )));
+    };
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let staking_asset_info = staking_asset_info(&pair_info);
+    let amount = if unbond_first {
+        let amount = query_bonded_amount(
+            deps,
+            Some(executor_addr.clone()),
+            &staking_asset_info,
+            amount,
+        )?;
+        messages.extend(query_unbond_msg(
+            deps,
+            env.clone(),
+            None,
+            staking_asset_info,
+            Some(AmountSpec::Exact(amount)),
+        )?);
+        amount
+    } else {
+        AmountSource::token_balance(amount, lp_token.clone(), Some(executor_addr.clone()))?
+            .resolve(&deps.querier)?
+    };
+
+    messages.extend(query_withdraw_liquidity_msg(
+        deps,
+        env.clone(),
+        None,
+        pair_contract,
+        lp_token.clone(),
+        Some(AmountSpec::Exact(amount)),
+    )?);
+
+    // the pair refunds each asset pro-rata to the withdrawn share
+    let total_share = query_supply(&deps.querier, lp_token)?;
+    if total_share.is_zero() {
+        return Err(StdError::generic_err("Pool is empty"));
+    }
+    // the pool may move before the withdrawal, only the guaranteed part of
+    // each refund is counted
+    let target_refund = guaranteed_return(
+        target_pool.multiply_ratio(amount, total_share),
+        slippage_tolerance,
+    )?;
+    let other_refund = guaranteed_return(
+        other_pool.amount.multiply_ratio(amount, total_share),
+        slippage_tolerance,
+    )?;
+    let swap_minimum_receive =
+        swap_minimum_receive(minimum_receive, target_refund, !other_refund.is_zero())?;
+    if other_refund.is_zero() {
+        return Ok(messages);
+    }
+
+    messages.extend(query_swap_operations_msg(
+        deps,
+        env,
+        executor_addr.clone(),
+        executor_addr,
+        Some(AmountSpec::Exact(other_refund)),
+        vec![SwapOperation::OraiSwap {
+            offer_asset_info: other_pool.info.clone(),
+            ask_asset_info: target_asset_info,
+        }],
+        SwapOptions {
+            minimum_receive: swap_minimum_receive,
+            ..SwapOptions::default()
+        },
+    )?);
+
+    Ok(messages)
+}
+
+/// Part of `minimum_receive` the swap of the other refund has to cover, the
+/// guaranteed `target_refund` counts towards it.
+fn swap_minimum_receive(
+    minimum_receive: Option<Uint128>,
+    target_refund: Uint128,
+    swaps: bool,
+) -> StdResult<Option<Uint128>> {
+    match minimum_receive {
+        Some(minimum_receive) if minimum_receive > target_refund => {
+            if !swaps {
+                return Err(StdError::generic_err(format!(
+                    "Refund of {} does not reach the minimum receive {}",
+                    target_refund, minimum_receive
+                )));
+            }
+            Ok(Some(minimum_receive.checked_sub(target_refund)?))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Withdraw the pending rewards of the `asset_info` staking pool, swap half of
+/// each reward into the other asset of the pair, provide both and bond the LP.
+pub fn query_compound_msg(
+    deps: Deps,
+    env: Env,
+    executor_addr: Addr,
+    asset_info: AssetInfo,
+    pair_contract: Addr,
+    slippage_tolerance: Option<Decimal>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let swap_router = deps.api.addr_humanize(&config.oraiswap_router)?;
+    let oraiswap_staking = deps.api.addr_humanize(&config.oraiswap_staking)?;
+    let pair_info = query_pair_info_from_pair(&deps.querier, pair_contract.clone())?;
+    if staking_asset_info(&pair_info) != asset_info {
+        return Err(StdError::generic_err(format!(
+            "{} is not the staking asset of pair {}",
+            asset_info, pair_contract
+        )));
+    }
+    let pools = pair_info.query_pools(&deps.querier, pair_contract.clone())?;
+
+    let rewards = match query_reward_info(
+        &deps.querier,
+        oraiswap_staking.clone(),
+        executor_addr.clone(),
+        asset_info.clone(),
+    )? {
+        Some(reward_info) => query_pending_rewards(deps, oraiswap_staking, reward_info)?,
+        None => vec![],
+    };
+    if rewards.is_empty() {
+        return Err(StdError::generic_err("No pending rewards"));
+    }
+
+    let mut messages = query_withdraw_msg(deps, env.clone(), Some(asset_info.clone()))?;
+    let mut deposits = [Uint128::zero(); 2];
+    let mut reserves = [pools[0].amount, pools[1].amount];
+    for reward in rewards {
+        let offer = match pools.iter().position(|pool| pool.info == reward.info) {
+            Some(offer) => offer,
+            None => {
+                return Err(StdError::generic_err(format!(
+                    "Reward {} is not an asset of pair {}",
+                    reward.info, pair_contract
+                )))
+            }
+        };
+        let ask = 1 - offer;
+        let swap_amount = reward.amount.multiply_ratio(1u128, 2u128);
+        deposits[offer] += reward.amount - swap_amount;
+        if swap_amount.is_zero() {
+            continue;
+        }
+
+        let operations = vec![SwapOperation::OraiSwap {
+            offer_asset_info: reward.info,
+            ask_asset_info: pools[ask].info.clone(),
+        }];
+        let return_amount = simulate_swap_operations(
+            &deps.querier,
+            swap_router.clone(),
+            swap_amount,
+            operations.clone(),
+        )?;
+        let minimum_receive = guaranteed_return(return_amount, slippage_tolerance)?;
+        messages.extend(query_swap_operations_msg(
+            deps,
+            env.clone(),
+            executor_addr.clone(),
+            executor_addr.clone(),
+            Some(AmountSpec::Exact(swap_amount)),
+            operations,
+            SwapOptions {
+                minimum_receive: Some(minimum_receive),
+                ..SwapOptions::default()
+            },
+        )?);
+        deposits[ask] += minimum_receive;
+        reserves[offer] += swap_amount;
+        reserves[ask] = reserves[ask].checked_sub(return_amount)?;
+    }
+
+    // as for ZapIn, provide in the pool ratio and bond the guaranteed share
+    let deposits = balanced_deposits(deposits, reserves)?;
+    let share = estimate_share(&deps, &pair_info, deposits, reserves)?;
+    let assets = [
+        Asset {
+            info: pools[0].info.clone(),
+            amount: deposits[0],
+        },
+        Asset {
+            info: pools[1].info.clone(),
+            amount: deposits[1],
+        },
+    ];
+    messages.extend(query_provide_liquidity_msg(
+        deps,
+        env.clone(),
+        None,
+        None,
+        pair_contract,
+        assets,
+        slippage_tolerance,
+        None,
+    )?);
+    messages.extend(query_bond_msg(
+        deps,
+        env,
+        None,
+        pair_info.liquidity_token,
+        asset_info,
+        Some(AmountSpec::Exact(guaranteed_return(
+            share,
+            slippage_tolerance,
+        )?)),
+    )?);
+
+    Ok(messages)
+}
+
+/// Rewards a `Withdraw` would pay out: the pending reward split across the
+/// pool's reward assets by their emission rate, plus what is already queued.
+pub fn query_pending_rewards(
+    deps: Deps,
+    oraiswap_staking: Addr,
+    reward_info: RewardInfoResponseItem,
+) -> StdResult<Vec<Asset>> {
+    let rewards_per_sec =
+        query_rewards_per_sec(&deps.querier, oraiswap_staking, reward_info.asset_info)?;
+    let total_per_sec = rewards_per_sec
+        .iter()
+        .fold(Uint128::zero(), |total, reward| total + reward.amount);
+
+    let mut rewards: Vec<Asset> = vec![];
+    let split = rewards_per_sec.into_iter().map(|reward| Asset {
+        amount: if total_per_sec.is_zero() {
+            Uint128::zero()
+        } else {
+            reward_info
+                .pending_reward
+                .multiply_ratio(reward.amount, total_per_sec)
+        },
+        info: reward.info,
+    });
+    for reward in split.chain(reward_info.pending_withdraw) {
+        if reward.amount.is_zero() {
+            continue;
+        }
+        match rewards.iter_mut().find(|asset| asset.info == reward.info) {
+            Some(asset) => asset.amount += reward.amount,
+            None => rewards.push(reward),
+        }
+    }
+
+    Ok(rewards)
+}
+
+/// Part of an amount estimated at build time guaranteed under
+/// `slippage_tolerance`, `DEFAULT_SLIPPAGE_TOLERANCE_PERMILLE` when unset: the pool
+/// moves before the messages execute, so the estimate itself is never used.
+fn guaranteed_return(
+    return_amount: Uint128,
+    slippage_tolerance: Option<Decimal>,
+) -> StdResult<Uint128> {
+    let slippage_tolerance =
+        slippage_tolerance.unwrap_or(Decimal::permille(DEFAULT_SLIPPAGE_TOLERANCE_PERMILLE));
+    if slippage_tolerance > Decimal::one() {
+        return Err(StdError::generic_err(
+            "Slippage tolerance must not exceed 1",
+        ));
+    }
+    Ok(return_amount * (Decimal::one() - slippage_tolerance))
+}
+
+/// Largest deposits of at most `available` in the ratio of `reserves`, both in
+/// the same asset order. The excess of the other leg stays with the provider
+/// instead of being donated to the pool.
+fn balanced_deposits(available: [Uint128; 2], reserves: [Uint128; 2]) -> StdResult<[Uint128; 2]> {
+    if reserves[0].is_zero() || reserves[1].is_zero() {
+        return Err(StdError::generic_err("Pool is empty"));
+    }
+
+    let matched = available[0].multiply_ratio(reserves[1], reserves[0]);
+    if matched <= available[1] {
+        Ok([available[0], matched])
+    } else {
+        Ok([
+            available[1].multiply_ratio(reserves[0], reserves[1]),
+            available[1],
+        ])
+    }
+}
+
+/// Amount of `amount` to swap so that what is left and the swap output match
+/// the pool ratio after the swap, for a constant product pool of `reserve`
+/// taking `commission_rate` from the output:
+/// `s = (sqrt(((2 - f) r)^2 + 4 (1 - f) a r) - (2 - f) r) / (2 (1 - f))`
+fn optimal_swap_amount(
+    amount: Uint128,
+    reserve: Uint128,
+    commission_rate: Decimal,
+) -> StdResult<Uint128> {
+    if reserve.is_zero() {
+        return Err(StdError::generic_err("Pool is empty"));
+    }
+    if commission_rate >= Decimal::one() {
+        return Err(StdError::generic_err("Invalid pair commission rate"));
+    }
+
+    // decimals are scaled to integers by 10^18
+    let scale = Uint512::from(Decimal::one().atomics());
+    let fee = Uint512::from(commission_rate.atomics());
+    let amount = Uint512::from(amount);
+    let reserve = Uint512::from(reserve);
+
+    let b = (scale + scale - fee) * reserve;
+    let c = Uint512::from(4u8) * (scale - fee) * amount * reserve * scale;
+    let root = (b * b + c).isqrt();
+    let swap_amount = (root - b) / (Uint512::from(2u8) * (scale - fee));
+
+    Ok(Uint128::try_from(swap_amount)?)
+}
+
+/// LP minted for `deposits` into a pool of `reserves`, both in the same asset order.
+fn estimate_share(
+    deps: &Deps,
+    pair_info: &PairInfo,
+    deposits: [Uint128; 2],
+    reserves: [Uint128; 2],
+) -> StdResult<Uint128> {
+    let total_share = query_supply(&deps.querier, pair_info.liquidity_token.clone())?;
+    if total_share.is_zero() || reserves[0].is_zero() || reserves[1].is_zero() {
+        return Err(StdError::generic_err("Pool is empty"));
+    }
+
+    Ok(std::cmp::min(
+        deposits[0].multiply_ratio(total_share, reserves[0]),
+        deposits[1].multiply_ratio(total_share, reserves[1]),
+    ))
+}
+
+/// OraiDEX staking pools are keyed by the non ORAI asset of the pair.
+pub fn staking_asset_info(pair_info: &PairInfo) -> AssetInfo {
+    let orai = AssetInfo::NativeToken {
+        denom: ORAI_DENOM.to_string(),
+    };
+    if pair_info.asset_infos[0] == orai {
+        pair_info.asset_infos[1].clone()
+    } else {
+        pair_info.asset_infos[0].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn optimal_swap_without_commission() {
+        // s = sqrt(r^2 + a r) - r
+        let swap_amount =
+            optimal_swap_amount(Uint128::new(1000), Uint128::new(1_000_000), Decimal::zero())
+                .unwrap();
+        assert_eq!(swap_amount, Uint128::new(499));
+    }
+
+    #[test]
+    fn optimal_swap_matches_pool_ratio() {
+        let amount: u128 = 1_000_000;
+        let offer_reserve: u128 = 1_000_000_000;
+        let ask_reserve: u128 = 2_000_000_000;
+        let commission_rate = Decimal::permille(3);
+
+        let swap_amount = optimal_swap_amount(
+            Uint128::new(amount),
+            Uint128::new(offer_reserve),
+            commission_rate,
+        )
+        .unwrap()
+        .u128();
+        let return_amount = Uint128::new(ask_reserve * swap_amount / (offer_reserve + swap_amount))
+            * (Decimal::one() - commission_rate);
+        let return_amount = return_amount.u128();
+
+        // what is left of the offer and the swap output are in the pool ratio
+        let left = (amount - swap_amount) * (ask_reserve - return_amount);
+        let right = return_amount * (offer_reserve + swap_amount);
+        assert!(left.abs_diff(right) * 100_000 < right);
+    }
+
+    #[test]
+    fn optimal_swap_rejects_invalid_pools() {
+        assert!(optimal_swap_amount(Uint128::new(1000), Uint128::zero(), Decimal::zero()).is_err());
+        assert!(
+            optimal_swap_amount(Uint128::new(1000), Uint128::new(1000), Decimal::one()).is_err()
+        );
+    }
+
+    #[test]
+    fn deposits_follow_pool_ratio() {
+        let reserves = [Uint128::new(1000), Uint128::new(2000)];
+        assert_eq!(
+            balanced_deposits([Uint128::new(100), Uint128::new(150)], reserves).unwrap(),
+            [Uint128::new(75), Uint128::new(150)]
+        );
+        assert_eq!(
+            balanced_deposits([Uint128::new(100), Uint128::new(500)], reserves).unwrap(),
+            [Uint128::new(100), Uint128::new(200)]
+        );
+        assert!(balanced_deposits([Uint128::new(100); 2], [Uint128::zero(); 2]).is_err());
+    }
+
+    #[test]
+    fn guaranteed_return_defaults_to_a_haircut() {
+        assert_eq!(
+            guaranteed_return(Uint128::new(1000), None).unwrap(),
+            Uint128::new(995)
+        );
+        assert_eq!(
+            guaranteed_return(Uint128::new(1000), Some(Decimal::percent(1))).unwrap(),
+            Uint128::new(990)
+        );
+        assert!(guaranteed_return(Uint128::new(1000), Some(Decimal::percent(101))).is_err());
+    }
+
+    #[test]
+    fn swap_minimum_receive_counts_the_refund() {
+        let refund = Uint128::new(100);
+        assert_eq!(swap_minimum_receive(None, refund, true).unwrap(), None);
+        assert_eq!(
+            swap_minimum_receive(Some(Uint128::new(80)), refund, false).unwrap(),
+            None
+        );
+        assert_eq!(
+            swap_minimum_receive(Some(Uint128::new(150)), refund, true).unwrap(),
+            Some(Uint128::new(50))
+        );
+        assert!(swap_minimum_receive(Some(Uint128::new(150)), refund, false).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, CosmosMsg, Uint128};
+use cosmwasm_std::{Addr, Binary, CosmosMsg};
+use operations_core::{operation::BatchMessagesResponse, AmountSpec};
 use orchai::orai_staking::{BondType, UnBondType};
 
 #[cw_serde]
@@ -27,6 +28,8 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(Vec<CosmosMsg>)]
     Messages { msg: Binary },
+    #[returns(BatchMessagesResponse)]
+    BatchMessages { msgs: Vec<Binary> },
     #[returns(ConfigResponse)]
     Config {},
 }
@@ -46,7 +49,7 @@ pub enum StakingOperations {
     Bond {
         sender: Option<Addr>,
         bond_type: BondType,
-        amount: Option<Uint128>,
+        amount: Option<AmountSpec>,
     },
     WithdrawUnbonded {
         unbond_type: UnBondType,
@@ -55,7 +58,7 @@ pub enum StakingOperations {
         executor_addr: Addr,
         sender: Addr,
         token: Addr,
-        amount: Option<Uint128>,
+        amount: Option<AmountSpec>,
     },
     /// return the accrued reward in uusd to the user.
     ClaimRewards {
@@ -65,7 +68,7 @@ pub enum StakingOperations {
         executor_addr: Addr,
         sender: Addr,
         from_token: Addr,
-        amount: Option<Uint128>,
+        amount: Option<AmountSpec>,
     },
 }
 
@@ -1,39 +1,59 @@
-use cosmwasm_std::{
-    to_binary, Addr, Coin, CosmosMsg, Deps, Env, StdError, StdResult, Uint128, WasmMsg,
-};
+use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, Deps, Env, StdError, StdResult, WasmMsg};
 use cw20::Cw20ExecuteMsg;
-use orchai::{
-    orai_staking::{BondType, OraiStakingExecuteMsg},
-    querier::query_token_balance,
-};
+use operations_core::{AmountSource, AmountSpec, Operation};
+use orchai::orai_staking::{BondType, OraiStakingExecuteMsg};
 
-use crate::state::CONFIG;
+use crate::{msg::StakingOperations, state::CONFIG};
+
+impl Operation for StakingOperations {
+    fn build(self, deps: Deps, env: Env) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            StakingOperations::Bond {
+                sender,
+                bond_type,
+                amount,
+            } => query_orai_staking_bond_msg(deps, env, sender, bond_type, amount),
+            StakingOperations::Unbond {
+                executor_addr,
+                sender,
+                token,
+                amount,
+            } => query_orai_staking_unbond_msg(deps, env, executor_addr, sender, token, amount),
+            StakingOperations::ClaimRewards { recipient } => {
+                query_orai_staking_claim_rewards_msg(deps, env, recipient)
+            }
+            StakingOperations::Convert {
+                executor_addr,
+                sender,
+                from_token,
+                amount,
+            } => {
+                query_orai_staking_convert_msg(deps, env, executor_addr, sender, from_token, amount)
+            }
+            StakingOperations::WithdrawUnbonded { .. } => Err(StdError::NotFound {
+                kind: "Operations not found".to_string(),
+            }),
+        }
+    }
+}
 
 pub fn query_orai_staking_bond_msg(
     deps: Deps,
     _env: Env,
     sender: Option<Addr>,
     bond_type: BondType,
-    amount: Option<Uint128>,
+    amount: Option<AmountSpec>,
 ) -> StdResult<Vec<CosmosMsg>> {
-    if sender.is_none() && amount.is_none() {
-        return Err(StdError::GenericErr {
-            msg: "Sender and amount is none!!!".to_string(),
-        });
-    }
     let config = CONFIG.load(deps.storage)?;
     let orai_staking_hub = deps.api.addr_humanize(&config.orai_staking_hub)?;
+    let amount = AmountSource::native_balance(amount, config.orai_staking_denom.clone(), sender)?
+        .resolve(&deps.querier)?;
     let msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: orai_staking_hub.to_string(),
         msg: to_binary(&OraiStakingExecuteMsg::Bond { bond_type })?,
         funds: vec![Coin {
-            denom: config.orai_staking_denom.clone(),
-            amount: amount.unwrap_or_else(|| {
-                deps.querier
-                    .query_balance(sender.unwrap().to_string(), config.orai_staking_denom)
-                    .unwrap()
-                    .amount
-            }),
+            denom: config.orai_staking_denom,
+            amount,
         }],
     });
 
@@ -46,14 +66,13 @@ pub fn query_orai_staking_unbond_msg(
     executor_addr: Addr,
     sender: Addr,
     token: Addr,
-    amount: Option<Uint128>,
+    amount: Option<AmountSpec>,
 ) -> StdResult<Vec<CosmosMsg>> {
     let config = CONFIG.load(deps.storage)?;
     let orai_staking_hub = deps.api.addr_humanize(&config.orai_staking_hub)?;
     let mut messages: Vec<CosmosMsg> = vec![];
-    let amount = amount.unwrap_or_else(|| {
-        query_token_balance(&deps.querier, token.clone(), sender.clone()).unwrap()
-    });
+    let amount = AmountSource::token_balance(amount, token.clone(), Some(sender.clone()))?
+        .resolve(&deps.querier)?;
 
     if sender != executor_addr {
         messages.push(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
@@ -104,14 +123,13 @@ pub fn query_orai_staking_convert_msg(
     executor_addr: Addr,
     sender: Addr,
     from_token: Addr,
-    amount: Option<Uint128>,
+    amount: Option<AmountSpec>,
 ) -> StdResult<Vec<CosmosMsg>> {
     let config = CONFIG.load(deps.storage)?;
     let orai_staking_hub = deps.api.addr_humanize(&config.orai_staking_hub)?;
 
-    let amount = amount.unwrap_or_else(|| {
-        query_token_balance(&deps.querier, from_token.clone(), sender.clone()).unwrap()
-    });
+    let amount = AmountSource::token_balance(amount, from_token.clone(), Some(sender.clone()))?
+        .resolve(&deps.querier)?;
 
     let mut messages: Vec<CosmosMsg> = vec![];
 
@@ -1,6 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::CanonicalAddr;
 use cw_storage_plus::Item;
+use operations_core::impl_owned_config;
 
 // put the length bytes at the first for compatibility with legacy singleton store
 pub const CONFIG: Item<Config> = Item::new("\u{0}\u{6}config");
@@ -13,3 +14,5 @@ pub struct Config {
     pub orai_staking_reward: CanonicalAddr,
     pub orai_staking_denom: String,
 }
+
+impl_owned_config!(Config);
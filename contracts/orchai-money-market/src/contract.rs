@@ -1,33 +1,29 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    to_binary, Addr, Api, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult,
+};
+use cw_storage_plus::Bound;
+use operations_core::{
+    instantiate_entry_point, migrate_entry_point,
+    operation::{build_batch_messages, build_messages},
+    ownership::{assert_owner, update_config},
+    Operation,
 };
 // use cw2::set_contract_version;
 
-use crate::custody_operations::query_custody_claim_rewards_msgs;
 use crate::error::ContractError;
-use crate::liquidation_operations::{
-    query_liquidation_activate_bids_msg, query_liquidation_claim_lending_rewards_msg,
-    query_liquidation_claim_liquidations_msg, query_liquidation_retract_bid_msg,
-    query_liquidation_submit_bid_msg,
-};
-use crate::market_operations::{
-    query_market_borrow_stable_msgs, query_market_claim_borrower_rewards_msgs,
-    query_market_claim_lender_rewards_msgs, query_market_deposit_stable_msgs,
-    query_market_redeem_stable_msgs, query_market_repay_stable_for_msgs,
-    query_market_repay_stable_msgs,
-};
+use crate::health::query_borrower_health;
 use crate::msg::{
-    CollateralInfoResponse, ConfigResponse, CustodyOperations, ExecuteMsg, InstantiateMsg,
-    LiquidationOperations, MarketOperations, MigrateMsg, OverseerOperations, QueryMsg,
+    CollateralInfoResponse, CollateralsResponse, ConfigResponse, ExecuteMsg, InstantiateMsg,
+    MigrateMsg, MoneyMarketOperation, QueryMsg,
 };
 
-use crate::overseer_operations::{
-    query_overseer_provide_and_lock_collateral_msg,
-    query_overseer_unlock_and_withdraw_collateral_msg,
-};
-use crate::state::{read_collateral_info, store_collateral_info, CollateralInfo, Config, CONFIG};
+use crate::state::{read_collateral_info, CollateralInfo, Config, COLLATERALS, CONFIG};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 /*
 // version info for migration info
@@ -35,29 +31,26 @@ const CONTRACT_NAME: &str = "crates.io:smart-wallet";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 */
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    msg: InstantiateMsg,
-) -> Result<Response, ContractError> {
-    CONFIG.save(
-        deps.storage,
-        &Config {
-            owner: deps.api.addr_canonicalize(msg.owner.as_str())?,
-            market: deps.api.addr_canonicalize(msg.market.as_str())?,
-            overseer: deps.api.addr_canonicalize(msg.overseer.as_str())?,
-            liquidation: deps.api.addr_canonicalize(msg.liquidation.as_str())?,
-            stable_addr: deps.api.addr_canonicalize(msg.stable_addr.as_str())?,
-            a_stable_contract: deps.api.addr_canonicalize(msg.a_stable_contract.as_str())?,
-            a_stable_contract_reward: deps
-                .api
-                .addr_canonicalize(msg.a_stable_contract_reward.as_str())?,
-        },
-    )?;
+instantiate_entry_point!(InstantiateMsg, CONFIG, config_from_msg);
 
-    Ok(Response::default())
+fn config_from_msg(api: &dyn Api, msg: InstantiateMsg) -> StdResult<Config> {
+    Ok(Config {
+        owner: api.addr_canonicalize(msg.owner.as_str())?,
+        market: api.addr_canonicalize(msg.market.as_str())?,
+        overseer: api.addr_canonicalize(msg.overseer.as_str())?,
+        liquidation: api.addr_canonicalize(msg.liquidation.as_str())?,
+        stable_addr: api.addr_canonicalize(msg.stable_addr.as_str())?,
+        a_stable_contract: api.addr_canonicalize(msg.a_stable_contract.as_str())?,
+        a_stable_contract_reward: api.addr_canonicalize(msg.a_stable_contract_reward.as_str())?,
+        oraiswap_router: msg
+            .oraiswap_router
+            .map(|router| api.addr_canonicalize(router.as_str()))
+            .transpose()?,
+        oracle: msg
+            .oracle
+            .map(|oracle| api.addr_canonicalize(oracle.as_str()))
+            .transpose()?,
+    })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -76,6 +69,8 @@ pub fn execute(
             stable_addr,
             a_stable_contract,
             a_stable_contract_reward,
+            oraiswap_router,
+            oracle,
         } => execute_update_config(
             deps,
             env,
@@ -87,14 +82,20 @@ pub fn execute(
             stable_addr,
             a_stable_contract,
             a_stable_contract_reward,
+            oraiswap_router,
+            oracle,
         ),
         ExecuteMsg::RegisterCollateral {
             collateral,
             custody_contract,
         } => execute_register_collateral(deps, env, info, collateral, custody_contract),
+        ExecuteMsg::DeregisterCollateral { collateral } => {
+            execute_deregister_collateral(deps, env, info, collateral)
+        }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
     _env: Env,
@@ -106,39 +107,43 @@ pub fn execute_update_config(
     stable_addr: Option<Addr>,
     a_stable_contract: Option<Addr>,
     a_stable_contract_reward: Option<Addr>,
+    oraiswap_router: Option<Addr>,
+    oracle: Option<Addr>,
 ) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
-    if deps.api.addr_humanize(&config.owner)? != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
-
-    if let Some(owner) = owner {
-        config.owner = deps.api.addr_canonicalize(owner.as_str())?;
-    }
-    if let Some(overseer) = overseer {
-        config.overseer = deps.api.addr_canonicalize(overseer.as_str())?;
-    }
-    if let Some(market) = market {
-        config.market = deps.api.addr_canonicalize(market.as_str())?;
-    }
-    if let Some(liquidation) = liquidation {
-        config.liquidation = deps.api.addr_canonicalize(liquidation.as_str())?;
-    }
-    if let Some(stable_addr) = stable_addr {
-        config.stable_addr = deps.api.addr_canonicalize(stable_addr.as_str())?;
-    }
-    if let Some(a_stable_contract) = a_stable_contract {
-        config.a_stable_contract = deps.api.addr_canonicalize(a_stable_contract.as_str())?;
-    }
-    if let Some(a_stable_contract_reward) = a_stable_contract_reward {
-        config.a_stable_contract_reward = deps
-            .api
-            .addr_canonicalize(a_stable_contract_reward.as_str())?;
-    }
-
-    CONFIG.save(deps.storage, &config)?;
-
-    Ok(Response::new().add_attribute("action", "update_config"))
+    Ok(update_config(
+        deps,
+        &info,
+        &CONFIG,
+        owner,
+        |api, config| {
+            if let Some(overseer) = overseer {
+                config.overseer = api.addr_canonicalize(overseer.as_str())?;
+            }
+            if let Some(market) = market {
+                config.market = api.addr_canonicalize(market.as_str())?;
+            }
+            if let Some(liquidation) = liquidation {
+                config.liquidation = api.addr_canonicalize(liquidation.as_str())?;
+            }
+            if let Some(stable_addr) = stable_addr {
+                config.stable_addr = api.addr_canonicalize(stable_addr.as_str())?;
+            }
+            if let Some(a_stable_contract) = a_stable_contract {
+                config.a_stable_contract = api.addr_canonicalize(a_stable_contract.as_str())?;
+            }
+            if let Some(a_stable_contract_reward) = a_stable_contract_reward {
+                config.a_stable_contract_reward =
+                    api.addr_canonicalize(a_stable_contract_reward.as_str())?;
+            }
+            if let Some(oraiswap_router) = oraiswap_router {
+                config.oraiswap_router = Some(api.addr_canonicalize(oraiswap_router.as_str())?);
+            }
+            if let Some(oracle) = oracle {
+                config.oracle = Some(api.addr_canonicalize(oracle.as_str())?);
+            }
+            Ok(())
+        },
+    )?)
 }
 pub fn execute_register_collateral(
     deps: DepsMut,
@@ -148,26 +153,44 @@ pub fn execute_register_collateral(
     custody_contract: Addr,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    if deps.api.addr_humanize(&config.owner)? != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    assert_owner(deps.api, &config, &info.sender)?;
 
     let collateral_raw = deps.api.addr_canonicalize(collateral.as_str())?;
-    store_collateral_info(
+    COLLATERALS.save(
         deps.storage,
-        &collateral_raw,
+        collateral_raw.as_slice(),
         &CollateralInfo {
             collateral: collateral_raw.clone(),
-            custody_contract: deps
-                .api
-                .addr_canonicalize(custody_contract.as_str())
-                .unwrap(),
+            custody_contract: deps.api.addr_canonicalize(custody_contract.as_str())?,
         },
     )?;
 
     Ok(Response::new().add_attributes(vec![("action", "register_collateral")]))
 }
 
+pub fn execute_deregister_collateral(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collateral: Addr,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_owner(deps.api, &config, &info.sender)?;
+
+    let collateral_raw = deps.api.addr_canonicalize(collateral.as_str())?;
+    if !COLLATERALS.has(deps.storage, collateral_raw.as_slice()) {
+        return Err(ContractError::CollateralNotFound {
+            collateral: collateral.to_string(),
+        });
+    }
+    COLLATERALS.remove(deps.storage, collateral_raw.as_slice());
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "deregister_collateral"),
+        ("collateral", collateral.as_str()),
+    ]))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -175,134 +198,33 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::CollateralInfo { collateral } => {
             to_binary(&query_collateral_info(deps, env, collateral)?)
         }
-        QueryMsg::Messages { msg } => match from_binary(&msg) {
-            Ok(MarketOperations::BorrowStable { borrow_amount, to }) => to_binary(
-                &query_market_borrow_stable_msgs(deps, env, borrow_amount, to)?,
-            ),
-            Ok(MarketOperations::DepositStable {
-                executor_addr,
-                sender,
-                stable_amount,
-            }) => to_binary(&query_market_deposit_stable_msgs(
-                deps,
-                env,
-                executor_addr,
-                sender,
-                stable_amount,
-            )?),
-            Ok(MarketOperations::RedeemStable {
-                executor_addr,
-                sender,
-                a_stable_amount,
-            }) => to_binary(&query_market_redeem_stable_msgs(
-                deps,
-                env,
-                executor_addr,
-                sender,
-                a_stable_amount,
-            )?),
-            Ok(MarketOperations::RepayStable {
-                amount,
-                executor_addr,
-                sender,
-            }) => to_binary(&query_market_repay_stable_msgs(
-                deps,
-                env,
-                amount,
-                executor_addr,
-                sender,
-            )?),
-            Ok(MarketOperations::RepayStableFor { amount, borrower }) => to_binary(
-                &query_market_repay_stable_for_msgs(deps, env, amount, borrower)?,
-            ),
-            Ok(MarketOperations::ClaimBorrowerRewards { to }) => {
-                to_binary(&query_market_claim_borrower_rewards_msgs(deps, env, to)?)
-            }
-            Ok(MarketOperations::ClaimLenderRewards { to }) => {
-                to_binary(&query_market_claim_lender_rewards_msgs(deps, env, to)?)
-            }
-
-            _ => match from_binary(&msg) {
-                Ok(CustodyOperations::ClaimRewards {
-                    collateral,
-                    recipient,
-                }) => to_binary(&query_custody_claim_rewards_msgs(
-                    deps, env, collateral, recipient,
-                )?),
-                _ => match from_binary(&msg) {
-                    Ok(LiquidationOperations::SubmitBid {
-                        sender,
-                        amount,
-                        collateral_token,
-                        premium_slot,
-                    }) => to_binary(&query_liquidation_submit_bid_msg(
-                        deps,
-                        env,
-                        sender,
-                        amount,
-                        collateral_token,
-                        premium_slot,
-                    )?),
-                    Ok(LiquidationOperations::RetractBid { bid_idx, amount }) => to_binary(
-                        &query_liquidation_retract_bid_msg(deps, env, bid_idx, amount)?,
-                    ),
-                    Ok(LiquidationOperations::ActivateBids {
-                        collateral_token,
-                        bids_idx,
-                    }) => to_binary(&query_liquidation_activate_bids_msg(
-                        deps,
-                        env,
-                        collateral_token,
-                        bids_idx,
-                    )?),
-                    Ok(LiquidationOperations::ClaimLiquidations {
-                        collateral_token,
-                        bids_idx,
-                    }) => to_binary(&query_liquidation_claim_liquidations_msg(
-                        deps,
-                        env,
-                        collateral_token,
-                        bids_idx,
-                    )?),
-                    Ok(LiquidationOperations::ClaimLendingRewards {
-                        collateral_token,
-                        bids_idx,
-                    }) => to_binary(&query_liquidation_claim_lending_rewards_msg(
-                        deps,
-                        env,
-                        collateral_token,
-                        bids_idx,
-                    )?),
-
-                    _ => match from_binary(&msg) {
-                        Ok(OverseerOperations::ProvideAndLockCollateral {
-                            executor_addr,
-                            sender,
-                            collateral,
-                            amount,
-                        }) => to_binary(&query_overseer_provide_and_lock_collateral_msg(
-                            deps,
-                            env,
-                            executor_addr,
-                            sender,
-                            collateral,
-                            amount,
-                        )?),
-                        Ok(OverseerOperations::UnlockAndWithdrawCollateral {
-                            sender,
-                            collateral,
-                            amount,
-                        }) => to_binary(&query_overseer_unlock_and_withdraw_collateral_msg(
-                            deps, env, sender, collateral, amount,
-                        )?),
+        QueryMsg::Collaterals { start_after, limit } => {
+            to_binary(&query_collaterals(deps, env, start_after, limit)?)
+        }
+        QueryMsg::BorrowerHealth { borrower } => {
+            to_binary(&query_borrower_health(deps, env, borrower)?)
+        }
+        QueryMsg::Messages { msg } => {
+            to_binary(&build_messages::<MoneyMarketOperation>(deps, env, &msg)?)
+        }
+        QueryMsg::BatchMessages { msgs } => to_binary(&build_batch_messages(
+            deps,
+            env,
+            &msgs,
+            build_messages::<MoneyMarketOperation>,
+        )?),
+    }
+}
 
-                        _ => Err(cosmwasm_std::StdError::NotFound {
-                            kind: "Operations not found".to_string(),
-                        }),
-                    },
-                },
-            },
-        },
+impl Operation for MoneyMarketOperation {
+    fn build(self, deps: Deps, env: Env) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            MoneyMarketOperation::Market(operation) => operation.build(deps, env),
+            MoneyMarketOperation::Custody(operation) => operation.build(deps, env),
+            MoneyMarketOperation::Liquidation(operation) => operation.build(deps, env),
+            MoneyMarketOperation::Overseer(operation) => operation.build(deps, env),
+            MoneyMarketOperation::Position(operation) => operation.build(deps, env),
+        }
     }
 }
 
@@ -317,6 +239,14 @@ pub fn query_config(deps: Deps, _env: Env) -> StdResult<ConfigResponse> {
         stable_addr: deps.api.addr_humanize(&config.stable_addr)?,
         a_stable_contract: deps.api.addr_humanize(&config.a_stable_contract)?,
         a_stable_contract_reward: deps.api.addr_humanize(&config.a_stable_contract_reward)?,
+        oraiswap_router: config
+            .oraiswap_router
+            .map(|router| deps.api.addr_humanize(&router))
+            .transpose()?,
+        oracle: config
+            .oracle
+            .map(|oracle| deps.api.addr_humanize(&oracle))
+            .transpose()?,
     })
 }
 
@@ -339,7 +269,33 @@ pub fn query_collateral_info(
     })
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn query_collaterals(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<CollateralsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|collateral| deps.api.addr_canonicalize(collateral.as_str()))
+        .transpose()?;
+    let start = start_after
+        .as_ref()
+        .map(|collateral_raw| Bound::exclusive(collateral_raw.as_slice()));
+
+    let collaterals = COLLATERALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (_, collateral_info) = item?;
+            Ok(CollateralInfoResponse {
+                collateral: deps.api.addr_humanize(&collateral_info.collateral)?,
+                custody_contract: deps.api.addr_humanize(&collateral_info.custody_contract)?,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(CollateralsResponse { collaterals })
 }
+
+migrate_entry_point!(MigrateMsg);
@@ -1,8 +1,21 @@
 use cosmwasm_std::{to_binary, Addr, CosmosMsg, Deps, Env, StdResult};
+use operations_core::Operation;
 use orchai::custody::CustodyExecuteMsg;
 
+use crate::msg::CustodyOperations;
 use crate::state::read_collateral_info;
 
+impl Operation for CustodyOperations {
+    fn build(self, deps: Deps, env: Env) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            CustodyOperations::ClaimRewards {
+                collateral,
+                recipient,
+            } => query_custody_claim_rewards_msgs(deps, env, collateral, recipient),
+        }
+    }
+}
+
 pub fn query_custody_claim_rewards_msgs(
     deps: Deps,
     _env: Env,
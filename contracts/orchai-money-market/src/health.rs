@@ -0,0 +1,92 @@
+use cosmwasm_std::{Addr, Decimal256, Deps, Env, Order, StdResult, Uint256};
+use orchai::querier::{
+    query_borrow_limit, query_custody_borrower, query_loan_amount, query_max_ltv, query_price,
+};
+
+use crate::{
+    msg::{BorrowerHealthResponse, CollateralHealth},
+    position_operations::load_oracle,
+    state::{COLLATERALS, CONFIG},
+};
+
+pub fn query_borrower_health(
+    deps: Deps,
+    env: Env,
+    borrower: Addr,
+) -> StdResult<BorrowerHealthResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let overseer = deps.api.addr_humanize(&config.overseer)?;
+    let stable_addr = deps.api.addr_humanize(&config.stable_addr)?;
+    let oracle = load_oracle(deps)?;
+
+    let loan_amount = query_loan_amount(
+        &deps.querier,
+        deps.api.addr_humanize(&config.market)?,
+        borrower.clone(),
+        env.block.height,
+    )?;
+    let borrow_limit = query_borrow_limit(
+        &deps.querier,
+        overseer.clone(),
+        borrower.clone(),
+        env.block.time.seconds(),
+    )?;
+
+    let mut collaterals: Vec<CollateralHealth> = vec![];
+    let mut collateral_value = Uint256::zero();
+    for item in COLLATERALS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, collateral_info) = item?;
+        let collateral = deps.api.addr_humanize(&collateral_info.collateral)?;
+        let custody_contract = deps.api.addr_humanize(&collateral_info.custody_contract)?;
+        let borrower_info =
+            query_custody_borrower(&deps.querier, custody_contract.clone(), borrower.clone())?;
+        let locked_amount = borrower_info.balance - borrower_info.spendable;
+        if locked_amount.is_zero() {
+            continue;
+        }
+
+        let price = query_price(
+            &deps.querier,
+            oracle.clone(),
+            collateral.to_string(),
+            stable_addr.to_string(),
+        )?;
+        let max_ltv = query_max_ltv(&deps.querier, overseer.clone(), collateral.clone())?;
+        collateral_value += locked_amount * price;
+        collaterals.push(CollateralHealth {
+            collateral,
+            custody_contract,
+            locked_amount,
+            price,
+            max_ltv,
+            liquidation_price: None,
+        });
+    }
+
+    // the limit the other collaterals provide has to fall short of the loan
+    // for a price drop of this one to reach it
+    for collateral in collaterals.iter_mut() {
+        let own_limit = collateral.locked_amount * collateral.price * collateral.max_ltv;
+        let other_limit = borrow_limit.saturating_sub(own_limit);
+        if loan_amount > other_limit && !collateral.max_ltv.is_zero() {
+            collateral.liquidation_price = Some(
+                Decimal256::from_ratio(loan_amount - other_limit, collateral.locked_amount)
+                    / collateral.max_ltv,
+            );
+        }
+    }
+
+    Ok(BorrowerHealthResponse {
+        borrower,
+        loan_amount,
+        borrow_limit,
+        collateral_value,
+        ltv: if collateral_value.is_zero() {
+            None
+        } else {
+            Some(Decimal256::from_ratio(loan_amount, collateral_value))
+        },
+        headroom: borrow_limit.saturating_sub(loan_amount),
+        collaterals,
+    })
+}
@@ -1,10 +1,14 @@
 pub mod contract;
 pub mod custody_operations;
 mod error;
+pub mod health;
 pub mod helpers;
 pub mod market_operations;
 pub mod msg;
 pub mod state;
+#[cfg(test)]
+mod testing;
 pub use crate::error::ContractError;
 pub mod liquidation_operations;
 pub mod overseer_operations;
+pub mod position_operations;
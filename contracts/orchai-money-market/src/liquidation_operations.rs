@@ -1,9 +1,46 @@
 use cosmwasm_std::{to_binary, Addr, CosmosMsg, Deps, Env, StdResult, Uint128, Uint256, WasmMsg};
-use orchai::{liquidation::LiquidationExecuteMsg, querier::query_token_balance};
+use operations_core::{AmountSource, AmountSpec, Operation};
+use orchai::liquidation::LiquidationExecuteMsg;
 
+use crate::msg::LiquidationOperations;
 use crate::state::CONFIG;
 use cw20::Cw20ExecuteMsg;
 
+impl Operation for LiquidationOperations {
+    fn build(self, deps: Deps, env: Env) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            LiquidationOperations::SubmitBid {
+                sender,
+                amount,
+                collateral_token,
+                premium_slot,
+            } => query_liquidation_submit_bid_msg(
+                deps,
+                env,
+                sender,
+                amount,
+                collateral_token,
+                premium_slot,
+            ),
+            LiquidationOperations::RetractBid { bid_idx, amount } => {
+                query_liquidation_retract_bid_msg(deps, env, bid_idx, amount)
+            }
+            LiquidationOperations::ActivateBids {
+                collateral_token,
+                bids_idx,
+            } => query_liquidation_activate_bids_msg(deps, env, collateral_token, bids_idx),
+            LiquidationOperations::ClaimLiquidations {
+                collateral_token,
+                bids_idx,
+            } => query_liquidation_claim_liquidations_msg(deps, env, collateral_token, bids_idx),
+            LiquidationOperations::ClaimLendingRewards {
+                collateral_token,
+                bids_idx,
+            } => query_liquidation_claim_lending_rewards_msg(deps, env, collateral_token, bids_idx),
+        }
+    }
+}
+
 pub fn query_liquidation_submit_bid_msg(
     deps: Deps,
     _env: Env,
@@ -14,13 +51,14 @@ pub fn query_liquidation_submit_bid_msg(
 ) -> StdResult<Vec<CosmosMsg>> {
     let config = CONFIG.load(deps.storage)?;
     let stable_addr = deps.api.addr_humanize(&config.stable_addr)?;
+    let amount =
+        AmountSource::token_balance(amount.map(AmountSpec::Exact), stable_addr.clone(), sender)?
+            .resolve(&deps.querier)?;
     let msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: stable_addr.to_string(),
         msg: to_binary(&Cw20ExecuteMsg::Send {
             contract: deps.api.addr_humanize(&config.liquidation)?.to_string(),
-            amount: amount.unwrap_or_else(|| {
-                query_token_balance(&deps.querier, stable_addr, sender.unwrap()).unwrap()
-            }),
+            amount,
             msg: to_binary(&LiquidationExecuteMsg::SubmitBid {
                 collateral_token: collateral_token.to_string(),
                 premium_slot,
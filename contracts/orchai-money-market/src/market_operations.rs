@@ -1,12 +1,92 @@
-use cosmwasm_std::{to_binary, Addr, CosmosMsg, Deps, Env, StdResult, Uint128, Uint256};
+use cosmwasm_std::{
+    to_binary, Addr, CosmosMsg, Decimal, Decimal256, Deps, Env, StdError, StdResult, Uint128,
+    Uint256,
+};
+use operations_core::{AmountSource, AmountSpec, Operation};
 use orchai::{
     market::{AStableRewardExecuteMsg, MarketExecuteMsg},
-    querier::query_token_balance,
+    querier::{query_loan_amount, query_token_balance},
 };
 
+use crate::health::query_borrower_health;
+use crate::msg::{BorrowerHealthResponse, CollateralSwap, MarketOperations};
+use crate::overseer_operations::query_overseer_unlock_and_withdraw_collateral_msg;
+use crate::position_operations::{assert_route, load_router, query_swap_msg};
 use crate::state::CONFIG;
 use cw20::Cw20ExecuteMsg;
 
+/// Share of the loan repaid on top to cover interest accrued until execution
+const DEFAULT_REPAY_BUFFER_PERMILLE: u64 = 1;
+
+impl Operation for MarketOperations {
+    fn build(self, deps: Deps, env: Env) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            MarketOperations::BorrowStable {
+                borrow_amount,
+                to,
+                max_ltv,
+                executor_addr,
+            } => {
+                if let Some(max_ltv) = max_ltv {
+                    let executor_addr = executor_addr.ok_or_else(|| {
+                        StdError::generic_err("executor_addr is required with max_ltv")
+                    })?;
+                    assert_ltv_after_borrow(
+                        deps,
+                        env.clone(),
+                        executor_addr,
+                        borrow_amount,
+                        max_ltv,
+                    )?;
+                }
+                query_market_borrow_stable_msgs(deps, env, borrow_amount, to)
+            }
+            MarketOperations::BorrowToLtv {
+                executor_addr,
+                target_ltv,
+                to,
+            } => query_market_borrow_to_ltv_msgs(deps, env, executor_addr, target_ltv, to),
+            MarketOperations::DepositStable {
+                executor_addr,
+                sender,
+                stable_amount,
+            } => query_market_deposit_stable_msgs(deps, env, executor_addr, sender, stable_amount),
+            MarketOperations::RedeemStable {
+                executor_addr,
+                sender,
+                a_stable_amount,
+            } => query_market_redeem_stable_msgs(deps, env, executor_addr, sender, a_stable_amount),
+            MarketOperations::RepayStable {
+                amount,
+                executor_addr,
+                sender,
+            } => query_market_repay_stable_msgs(deps, env, amount, executor_addr, sender),
+            MarketOperations::RepayAll {
+                executor_addr,
+                sender,
+                buffer,
+                collateral_swap,
+            } => query_market_repay_all_msgs(
+                deps,
+                env,
+                executor_addr,
+                sender,
+                buffer,
+                collateral_swap,
+            ),
+            MarketOperations::RepayStableFor { amount, borrower } => {
+                query_market_repay_stable_for_msgs(deps, env, amount, borrower)
+            }
+            MarketOperations::ClaimBorrowerRewards { to } => {
+                query_market_claim_borrower_rewards_msgs(deps, env, to)
+            }
+            MarketOperations::ClaimLenderRewards { to } => {
+                query_market_claim_lender_rewards_msgs(deps, env, to)
+            }
+        }
+    }
+}
+
 pub fn query_market_borrow_stable_msgs(
     deps: Deps,
     _env: Env,
@@ -22,20 +102,65 @@ pub fn query_market_borrow_stable_msgs(
     Ok(vec![msg])
 }
 
+/// Largest loan of a borrower at `ltv` of its collateral value, never above
+/// the overseer borrow limit.
+fn max_loan_at_ltv(health: &BorrowerHealthResponse, ltv: Decimal) -> Uint256 {
+    std::cmp::min(
+        health.collateral_value * Decimal256::from(ltv),
+        health.borrow_limit,
+    )
+}
+
+fn assert_ltv_after_borrow(
+    deps: Deps,
+    env: Env,
+    executor_addr: Addr,
+    borrow_amount: Uint256,
+    max_ltv: Decimal,
+) -> StdResult<()> {
+    let health = query_borrower_health(deps, env, executor_addr)?;
+    if health.loan_amount + borrow_amount > max_loan_at_ltv(&health, max_ltv) {
+        return Err(StdError::generic_err(format!(
+            "Borrowing {} would exceed the max ltv {}",
+            borrow_amount, max_ltv
+        )));
+    }
+    Ok(())
+}
+
+pub fn query_market_borrow_to_ltv_msgs(
+    deps: Deps,
+    env: Env,
+    executor_addr: Addr,
+    target_ltv: Decimal,
+    to: Option<String>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let health = query_borrower_health(deps, env.clone(), executor_addr)?;
+    let borrow_amount = max_loan_at_ltv(&health, target_ltv).saturating_sub(health.loan_amount);
+    if borrow_amount.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "Loan is already at or above the target ltv {}",
+            target_ltv
+        )));
+    }
+
+    query_market_borrow_stable_msgs(deps, env, borrow_amount, to)
+}
+
 pub fn query_market_deposit_stable_msgs(
     deps: Deps,
     _env: Env,
     executor_addr: Addr,
     sender: Addr,
-    stable_amount: Option<Uint128>,
+    stable_amount: Option<AmountSpec>,
 ) -> StdResult<Vec<CosmosMsg>> {
     let config = CONFIG.load(deps.storage)?;
     let stable_addr = deps.api.addr_humanize(&config.stable_addr)?;
     let mut messages: Vec<CosmosMsg> = vec![];
 
-    let stable_amount = stable_amount.unwrap_or_else(|| {
-        query_token_balance(&deps.querier, stable_addr.clone(), sender.clone()).unwrap()
-    });
+    let stable_amount =
+        AmountSource::token_balance(stable_amount, stable_addr.clone(), Some(sender.clone()))?
+            .resolve(&deps.querier)?;
 
     if sender != executor_addr {
         messages.push(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
@@ -66,15 +191,15 @@ pub fn query_market_redeem_stable_msgs(
     _env: Env,
     executor_addr: Addr,
     sender: Addr,
-    a_stable_amount: Option<Uint128>,
+    a_stable_amount: Option<AmountSpec>,
 ) -> StdResult<Vec<CosmosMsg>> {
     let config = CONFIG.load(deps.storage)?;
     let a_stable_addr = deps.api.addr_humanize(&config.a_stable_contract)?;
     let mut messages: Vec<CosmosMsg> = vec![];
 
-    let a_stable_amount = a_stable_amount.unwrap_or_else(|| {
-        query_token_balance(&deps.querier, a_stable_addr.clone(), sender.clone()).unwrap()
-    });
+    let a_stable_amount =
+        AmountSource::token_balance(a_stable_amount, a_stable_addr.clone(), Some(sender.clone()))?
+            .resolve(&deps.querier)?;
 
     if sender != executor_addr {
         messages.push(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
@@ -135,6 +260,158 @@ pub fn query_market_repay_stable_msgs(
     Ok(messages)
 }
 
+pub fn query_market_repay_all_msgs(
+    deps: Deps,
+    env: Env,
+    executor_addr: Addr,
+    sender: Addr,
+    buffer: Option<Decimal>,
+    collateral_swap: Option<CollateralSwap>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let stable_addr = deps.api.addr_humanize(&config.stable_addr)?;
+
+    let loan_amount = Uint128::try_from(query_loan_amount(
+        &deps.querier,
+        deps.api.addr_humanize(&config.market)?,
+        executor_addr.clone(),
+        env.block.height,
+    )?)?;
+    if loan_amount.is_zero() {
+        return Err(StdError::generic_err("No loan to repay"));
+    }
+    let buffer = buffer.unwrap_or_else(|| Decimal::permille(DEFAULT_REPAY_BUFFER_PERMILLE));
+    let amount = loan_amount + loan_amount * buffer;
+
+    // a wallet without stable is the case the collateral swap exists for
+    let balance = query_token_balance(&deps.querier, stable_addr.clone(), sender.clone())?;
+    if balance >= amount {
+        return query_market_repay_stable_msgs(deps, env, amount, executor_addr, sender);
+    }
+
+    let collateral_swap = collateral_swap.ok_or_else(|| {
+        StdError::generic_err(format!(
+            "Stable balance {} does not cover the loan {}",
+            balance, amount
+        ))
+    })?;
+    let mut messages = query_collateral_swap_msgs(
+        deps,
+        env.clone(),
+        &executor_addr,
+        &stable_addr,
+        amount - balance,
+        collateral_swap,
+    )?;
+
+    // the swapped stable already sits with the executor
+    if sender != executor_addr && !balance.is_zero() {
+        messages.push(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+            contract_addr: stable_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: sender.to_string(),
+                recipient: executor_addr.to_string(),
+                amount: balance,
+            })?,
+            funds: vec![],
+        }))
+    }
+    messages.extend(query_market_repay_stable_msgs(
+        deps,
+        env,
+        amount,
+        executor_addr.clone(),
+        executor_addr,
+    )?);
+    Ok(messages)
+}
+
+/// Unlock and sell enough collateral to receive at least `gap` stable.
+fn query_collateral_swap_msgs(
+    deps: Deps,
+    env: Env,
+    executor_addr: &Addr,
+    stable_addr: &Addr,
+    gap: Uint128,
+    collateral_swap: CollateralSwap,
+) -> StdResult<Vec<CosmosMsg>> {
+    let CollateralSwap {
+        collateral,
+        swap_route,
+        max_slippage,
+    } = collateral_swap;
+    assert_route(&swap_route, &collateral, stable_addr)?;
+    let router = load_router(deps)?;
+
+    // the overseer refuses an unlock that takes the loan over the borrow limit
+    let health = query_borrower_health(deps, env.clone(), executor_addr.clone())?;
+    if health.headroom.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "No borrow limit headroom to unlock {}, the loan {} is at the limit {}",
+            collateral, health.loan_amount, health.borrow_limit
+        )));
+    }
+    let collateral_health = health
+        .collaterals
+        .iter()
+        .find(|item| item.collateral == collateral)
+        .ok_or_else(|| StdError::generic_err(format!("No {} locked to unlock", collateral)))?;
+    let price = collateral_health.price;
+    if price.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "{} has no price",
+            collateral
+        )));
+    }
+
+    // start from the oracle price and scale once by the simulated shortfall
+    let mut unlock_amount = Uint128::try_from(Uint256::from(gap) * (Decimal256::one() / price))?;
+    let (mut swap_msg, mut received) = query_swap_msg(
+        deps,
+        &router,
+        &collateral,
+        unlock_amount,
+        &swap_route,
+        Some(max_slippage),
+    )?;
+    if received < gap && !received.is_zero() {
+        unlock_amount = unlock_amount.multiply_ratio(gap, received) + Uint128::one();
+        (swap_msg, received) = query_swap_msg(
+            deps,
+            &router,
+            &collateral,
+            unlock_amount,
+            &swap_route,
+            Some(max_slippage),
+        )?;
+    }
+    if received < gap {
+        return Err(StdError::generic_err(format!(
+            "Selling {} {} does not cover the missing {} stable",
+            unlock_amount, collateral, gap
+        )));
+    }
+    let unlock_amount_256 = Uint256::from(unlock_amount);
+    if unlock_amount_256 > collateral_health.locked_amount
+        || unlock_amount_256 * price * collateral_health.max_ltv > health.headroom
+    {
+        return Err(StdError::generic_err(format!(
+            "Unlocking {} {} exceeds the borrow limit headroom {}",
+            unlock_amount, collateral, health.headroom
+        )));
+    }
+
+    let mut messages = query_overseer_unlock_and_withdraw_collateral_msg(
+        deps,
+        env,
+        Some(executor_addr.clone()),
+        collateral,
+        Some(AmountSpec::Exact(unlock_amount)),
+    )?;
+    messages.push(swap_msg);
+    Ok(messages)
+}
+
 pub fn query_market_repay_stable_for_msgs(
     deps: Deps,
     _env: Env,
@@ -184,3 +461,78 @@ pub fn query_market_claim_lender_rewards_msgs(
     });
     Ok(vec![msg])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{mock_deps, route, MockPosition, COLLATERAL, EXECUTOR, STABLE};
+    use cosmwasm_std::testing::mock_env;
+
+    fn health(
+        loan_amount: u128,
+        borrow_limit: u128,
+        collateral_value: u128,
+    ) -> BorrowerHealthResponse {
+        BorrowerHealthResponse {
+            borrower: Addr::unchecked("borrower"),
+            loan_amount: Uint256::from(loan_amount),
+            borrow_limit: Uint256::from(borrow_limit),
+            collateral_value: Uint256::from(collateral_value),
+            ltv: None,
+            headroom: Uint256::from(borrow_limit.saturating_sub(loan_amount)),
+            collaterals: vec![],
+        }
+    }
+
+    #[test]
+    fn max_loan_at_ltv_stays_within_the_borrow_limit() {
+        let health = health(100, 600, 1000);
+        assert_eq!(
+            max_loan_at_ltv(&health, Decimal::percent(50)),
+            Uint256::from(500u128)
+        );
+        assert_eq!(
+            max_loan_at_ltv(&health, Decimal::percent(70)),
+            Uint256::from(600u128)
+        );
+    }
+
+    #[test]
+    fn repay_all_swaps_collateral_without_stable() {
+        let deps = mock_deps(MockPosition {
+            loan: 100,
+            locked: 1000,
+            ..MockPosition::default()
+        });
+        let executor = Addr::unchecked(EXECUTOR);
+        let messages = query_market_repay_all_msgs(
+            deps.as_ref(),
+            mock_env(),
+            executor.clone(),
+            executor,
+            None,
+            Some(CollateralSwap {
+                collateral: Addr::unchecked(COLLATERAL),
+                swap_route: route(COLLATERAL, STABLE),
+                max_slippage: Decimal::percent(1),
+            }),
+        )
+        .unwrap();
+
+        // unlock, withdraw, swap and repay, no transfer of the empty balance
+        assert_eq!(messages.len(), 4);
+        assert_eq!(
+            messages[3],
+            CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+                contract_addr: STABLE.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "market".to_string(),
+                    amount: Uint128::new(100),
+                    msg: to_binary(&MarketExecuteMsg::RepayStable {}).unwrap(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, CosmosMsg, Uint128, Uint256};
+use cosmwasm_std::{Addr, Binary, CosmosMsg, Decimal, Decimal256, Uint128, Uint256};
+use operations_core::{operation::BatchMessagesResponse, AmountSpec};
+use oraidex::router::SwapOperation;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -10,14 +12,20 @@ pub struct InstantiateMsg {
     pub stable_addr: Addr,
     pub a_stable_contract: Addr,
     pub a_stable_contract_reward: Addr,
+    pub oraiswap_router: Option<Addr>,
+    pub oracle: Option<Addr>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
+    /// Register a collateral, or replace the custody of a registered one
     RegisterCollateral {
         collateral: Addr,
         custody_contract: Addr,
     },
+    DeregisterCollateral {
+        collateral: Addr,
+    },
     UpdateConfig {
         owner: Option<Addr>,
         overseer: Option<Addr>,
@@ -26,6 +34,8 @@ pub enum ExecuteMsg {
         stable_addr: Option<Addr>,
         a_stable_contract: Option<Addr>,
         a_stable_contract_reward: Option<Addr>,
+        oraiswap_router: Option<Addr>,
+        oracle: Option<Addr>,
     },
 }
 
@@ -34,10 +44,21 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(Vec<CosmosMsg>)]
     Messages { msg: Binary },
+    #[returns(BatchMessagesResponse)]
+    BatchMessages { msgs: Vec<Binary> },
     #[returns(ConfigResponse)]
     Config {},
     #[returns(CollateralInfoResponse)]
     CollateralInfo { collateral: Addr },
+    #[returns(CollateralsResponse)]
+    Collaterals {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+    /// Loan, borrow limit and collateral of `borrower` across every
+    /// registered custody, priced by the configured oracle
+    #[returns(BorrowerHealthResponse)]
+    BorrowerHealth { borrower: Addr },
 }
 
 #[cw_serde]
@@ -49,6 +70,8 @@ pub struct ConfigResponse {
     pub stable_addr: Addr,
     pub a_stable_contract: Addr,
     pub a_stable_contract_reward: Addr,
+    pub oraiswap_router: Option<Addr>,
+    pub oracle: Option<Addr>,
 }
 #[cw_serde]
 pub struct CollateralInfoResponse {
@@ -56,11 +79,65 @@ pub struct CollateralInfoResponse {
     pub custody_contract: Addr,
 }
 
+#[cw_serde]
+pub struct CollateralsResponse {
+    pub collaterals: Vec<CollateralInfoResponse>,
+}
+
+#[cw_serde]
+pub struct CollateralHealth {
+    pub collateral: Addr,
+    pub custody_contract: Addr,
+    pub locked_amount: Uint256,
+    /// Stable token per collateral token
+    pub price: Decimal256,
+    pub max_ltv: Decimal256,
+    /// Price at which the loan reaches the borrow limit, other collaterals
+    /// unchanged. `None` when this collateral alone cannot trigger it.
+    pub liquidation_price: Option<Decimal256>,
+}
+
+#[cw_serde]
+pub struct BorrowerHealthResponse {
+    pub borrower: Addr,
+    pub loan_amount: Uint256,
+    pub borrow_limit: Uint256,
+    pub collateral_value: Uint256,
+    /// `None` without locked collateral
+    pub ltv: Option<Decimal256>,
+    /// Stable token that can still be borrowed
+    pub headroom: Uint256,
+    pub collaterals: Vec<CollateralHealth>,
+}
+
+/// Operation json accepted by `QueryMsg::Messages`, namespaced by the
+/// money market contract it targets, e.g. `{"market": {"borrow_stable": {...}}}`.
+#[cw_serde]
+pub enum MoneyMarketOperation {
+    Market(MarketOperations),
+    Custody(CustodyOperations),
+    Liquidation(LiquidationOperations),
+    Overseer(OverseerOperations),
+    Position(PositionOperations),
+}
+
 #[cw_serde]
 pub enum MarketOperations {
     BorrowStable {
         borrow_amount: Uint256,
         to: Option<String>,
+        /// Fail if the loan of `executor_addr` would exceed this ltv of its
+        /// collateral value, or its borrow limit, afterwards
+        max_ltv: Option<Decimal>,
+        /// The account executing the borrow, required with `max_ltv`
+        executor_addr: Option<Addr>,
+    },
+    /// Borrow what takes the loan of `executor_addr` to `target_ltv` of its
+    /// collateral value, within the overseer borrow limit
+    BorrowToLtv {
+        executor_addr: Addr,
+        target_ltv: Decimal,
+        to: Option<String>,
     },
     ClaimBorrowerRewards {
         to: Option<String>,
@@ -71,24 +148,49 @@ pub enum MarketOperations {
     RedeemStable {
         executor_addr: Addr,
         sender: Addr,
-        a_stable_amount: Option<Uint128>,
+        a_stable_amount: Option<AmountSpec>,
     },
     DepositStable {
         executor_addr: Addr,
         sender: Addr,
-        stable_amount: Option<Uint128>,
+        stable_amount: Option<AmountSpec>,
     },
     RepayStable {
         amount: Uint128,
         executor_addr: Addr,
         sender: Addr,
     },
+    /// Repay the whole loan of `executor_addr` plus `buffer` of it for the
+    /// interest accrued until execution, the market refunds the excess
+    RepayAll {
+        executor_addr: Addr,
+        sender: Addr,
+        /// Defaults to 0.1%
+        buffer: Option<Decimal>,
+        /// Unlock and sell collateral for what the stable balance of `sender`
+        /// does not cover
+        collateral_swap: Option<CollateralSwap>,
+    },
     RepayStableFor {
         amount: Uint128,
         borrower: String,
     },
 }
 
+#[cw_serde]
+pub struct CollateralAmount {
+    pub collateral: Addr,
+    pub amount: Option<AmountSpec>,
+}
+
+#[cw_serde]
+pub struct CollateralSwap {
+    pub collateral: Addr,
+    /// Route from `collateral` to the stable token
+    pub swap_route: Vec<SwapOperation>,
+    pub max_slippage: Decimal,
+}
+
 #[cw_serde]
 pub enum CustodyOperations {
     /// return the accrued reward in uusd to the user.
@@ -133,12 +235,52 @@ pub enum OverseerOperations {
         executor_addr: Addr,
         sender: Addr,
         collateral: Addr,
-        amount: Option<Uint128>,
+        amount: Option<AmountSpec>,
+    },
+    /// Deposit every collateral into its custody and lock them all with a
+    /// single overseer message
+    ProvideAndLockCollaterals {
+        executor_addr: Addr,
+        sender: Addr,
+        collaterals: Vec<CollateralAmount>,
     },
     UnlockAndWithdrawCollateral {
         sender: Option<Addr>,
         collateral: Addr,
-        amount: Option<Uint128>,
+        amount: Option<AmountSpec>,
+    },
+}
+
+/// Loops spanning the overseer, the market and the OraiDEX router. Collateral
+/// is priced in the stable token by the configured oracle.
+#[cw_serde]
+pub enum PositionOperations {
+    /// Lock `initial_amount`, then up to `max_iterations` times borrow until the
+    /// loan of `executor_addr` reaches `target_ltv` of its locked `collateral`,
+    /// swap it along `swap_route` into the collateral and lock the guaranteed
+    /// output
+    Leverage {
+        executor_addr: Addr,
+        collateral: Addr,
+        initial_amount: Option<AmountSpec>,
+        target_ltv: Decimal,
+        max_iterations: u8,
+        /// Route from the stable token to `collateral`
+        swap_route: Vec<SwapOperation>,
+        /// Per swap, defaults to 0.5%
+        max_slippage: Option<Decimal>,
+    },
+    /// Up to `max_iterations` times unlock the collateral above `target_ltv`,
+    /// swap it along `swap_route` into the stable token and repay
+    Deleverage {
+        executor_addr: Addr,
+        collateral: Addr,
+        target_ltv: Decimal,
+        max_iterations: u8,
+        /// Route from `collateral` to the stable token
+        swap_route: Vec<SwapOperation>,
+        /// Per swap, defaults to 0.5%
+        max_slippage: Option<Decimal>,
     },
 }
 
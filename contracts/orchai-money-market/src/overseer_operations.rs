@@ -1,66 +1,137 @@
-use std::str::FromStr;
-
 use cosmwasm_std::{to_binary, Addr, CosmosMsg, Deps, Env, StdError, StdResult, Uint128, WasmMsg};
 use cw20::Cw20ExecuteMsg;
+use operations_core::{
+    amount::{require_balance, require_holder},
+    AmountSource, AmountSpec, Operation,
+};
 use orchai::{
-    custody::CustodyExecuteMsg,
-    overseer::OverseerExecuteMsg,
-    querier::{query_custody_borrower, query_token_balance},
+    custody::CustodyExecuteMsg, overseer::OverseerExecuteMsg, querier::query_custody_borrower,
+    tokens::TokensHuman,
 };
 
+use crate::msg::{CollateralAmount, OverseerOperations};
 use crate::state::{read_collateral_info, CONFIG};
 
+impl Operation for OverseerOperations {
+    fn build(self, deps: Deps, env: Env) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            OverseerOperations::ProvideAndLockCollateral {
+                executor_addr,
+                sender,
+                collateral,
+                amount,
+            } => query_overseer_provide_and_lock_collateral_msg(
+                deps,
+                env,
+                executor_addr,
+                sender,
+                collateral,
+                amount,
+            ),
+            OverseerOperations::ProvideAndLockCollaterals {
+                executor_addr,
+                sender,
+                collaterals,
+            } => query_overseer_provide_and_lock_collaterals_msg(
+                deps,
+                env,
+                executor_addr,
+                sender,
+                collaterals,
+            ),
+            OverseerOperations::UnlockAndWithdrawCollateral {
+                sender,
+                collateral,
+                amount,
+            } => query_overseer_unlock_and_withdraw_collateral_msg(
+                deps, env, sender, collateral, amount,
+            ),
+        }
+    }
+}
+
 pub fn query_overseer_provide_and_lock_collateral_msg(
     deps: Deps,
-    _env: Env,
+    env: Env,
     executor_addr: Addr,
     sender: Addr,
     collateral: Addr,
-    amount: Option<Uint128>,
+    amount: Option<AmountSpec>,
+) -> StdResult<Vec<CosmosMsg>> {
+    query_overseer_provide_and_lock_collaterals_msg(
+        deps,
+        env,
+        executor_addr,
+        sender,
+        vec![CollateralAmount { collateral, amount }],
+    )
+}
+
+/// Deposits every collateral into its custody contract, then locks them all
+/// with a single `LockCollateral` message to the overseer.
+pub fn query_overseer_provide_and_lock_collaterals_msg(
+    deps: Deps,
+    _env: Env,
+    executor_addr: Addr,
+    sender: Addr,
+    collaterals: Vec<CollateralAmount>,
 ) -> StdResult<Vec<CosmosMsg>> {
     let config = CONFIG.load(deps.storage)?;
 
     let mut messages: Vec<CosmosMsg> = vec![];
-
-    let collateral_raw = deps.api.addr_canonicalize(collateral.as_str())?;
-    let collateral_info = read_collateral_info(deps.storage, &collateral_raw)?;
-
-    let amount = amount.unwrap_or_else(|| {
-        query_token_balance(&deps.querier, collateral.clone(), sender.clone()).unwrap()
-    });
-
-    if sender != executor_addr {
+    let mut locks: TokensHuman = vec![];
+
+    for CollateralAmount { collateral, amount } in collaterals {
+        if locks.iter().any(|(addr, _)| addr == collateral.as_str()) {
+            return Err(StdError::generic_err(format!(
+                "Duplicate collateral {}",
+                collateral
+            )));
+        }
+
+        let collateral_raw = deps.api.addr_canonicalize(collateral.as_str())?;
+        let collateral_info = read_collateral_info(deps.storage, &collateral_raw)?;
+
+        let amount = AmountSource::token_balance(amount, collateral.clone(), Some(sender.clone()))?
+            .resolve(&deps.querier)?;
+
+        if sender != executor_addr {
+            messages.push(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+                contract_addr: collateral.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: sender.to_string(),
+                    recipient: executor_addr.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            }))
+        }
+
+        // add deposit collateral msg
         messages.push(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
             contract_addr: collateral.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
-                owner: sender.to_string(),
-                recipient: executor_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: deps
+                    .api
+                    .addr_humanize(&collateral_info.custody_contract)?
+                    .to_string(),
                 amount,
+                msg: to_binary(&CustodyExecuteMsg::DepositCollateral {})?,
             })?,
             funds: vec![],
-        }))
+        }));
+
+        locks.push((collateral.to_string(), amount.into()));
     }
 
-    // add deposit collateral msg
-    messages.push(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
-        contract_addr: collateral.to_string(),
-        msg: to_binary(&Cw20ExecuteMsg::Send {
-            contract: deps
-                .api
-                .addr_humanize(&collateral_info.custody_contract)?
-                .to_string(),
-            amount,
-            msg: to_binary(&CustodyExecuteMsg::DepositCollateral {})?,
-        })?,
-        funds: vec![],
-    }));
+    if locks.is_empty() {
+        return Err(StdError::generic_err("No collaterals to lock"));
+    }
 
     // add lock collateral msg
     messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: deps.api.addr_humanize(&config.overseer)?.to_string(),
-        msg: to_binary(&OverseerExecuteMsg::LockCollateral {
-            collaterals: vec![(collateral.to_string(), amount.into())],
-        })?,
+        msg: to_binary(&OverseerExecuteMsg::LockCollateral { collaterals: locks })?,
         funds: vec![],
     }));
 
@@ -72,33 +143,29 @@ pub fn query_overseer_unlock_and_withdraw_collateral_msg(
     _env: Env,
     sender: Option<Addr>,
     collateral: Addr,
-    amount: Option<Uint128>,
+    amount: Option<AmountSpec>,
 ) -> StdResult<Vec<CosmosMsg>> {
     let config = CONFIG.load(deps.storage)?;
 
-    if sender.is_none() && amount.is_none() {
-        return Err(StdError::GenericErr {
-            msg: "Sender and amount is none!!!".to_string(),
-        });
-    }
     let mut messages: Vec<CosmosMsg> = vec![];
 
     let collateral_raw = deps.api.addr_canonicalize(collateral.as_str())?;
     let collateral_info = read_collateral_info(deps.storage, &collateral_raw)?;
 
     // add unlock_collateral msg
-    let amount = amount.unwrap_or_else(|| {
-        let borrower_info = query_custody_borrower(
-            &deps.querier,
-            deps.api
-                .addr_humanize(&collateral_info.custody_contract)
-                .unwrap(),
-            sender.unwrap(),
-        )
-        .unwrap();
-        return Uint128::from_str(&(borrower_info.balance - borrower_info.spendable).to_string())
-            .unwrap();
-    });
+    let amount = match amount.unwrap_or_default() {
+        AmountSpec::Exact(amount) => amount,
+        spec => {
+            let sender = require_holder(sender)?;
+            let borrower_info = query_custody_borrower(
+                &deps.querier,
+                deps.api.addr_humanize(&collateral_info.custody_contract)?,
+                sender.clone(),
+            )?;
+            let locked = Uint128::try_from(borrower_info.balance - borrower_info.spendable)?;
+            spec.apply(require_balance(locked, collateral.to_string(), &sender)?)?
+        }
+    };
     messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: deps.api.addr_humanize(&config.overseer)?.to_string(),
         msg: to_binary(&OverseerExecuteMsg::UnlockCollateral {
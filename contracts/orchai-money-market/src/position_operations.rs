@@ -0,0 +1,484 @@
+use cosmwasm_std::{
+    to_binary, Addr, CosmosMsg, Decimal, Decimal256, Deps, Env, StdError, StdResult, Uint128,
+    Uint256, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use operations_core::{AmountSource, AmountSpec, Operation};
+use oraidex::{
+    asset::AssetInfo,
+    querier::simulate_swap_operations,
+    router::{OraiswapExecuteMsg, SwapOperation},
+};
+use orchai::querier::{
+    query_custody_borrower, query_loan_amount, query_max_ltv, query_price, query_token_balance,
+};
+
+use crate::{
+    market_operations::{query_market_borrow_stable_msgs, query_market_repay_stable_msgs},
+    msg::PositionOperations,
+    overseer_operations::{
+        query_overseer_provide_and_lock_collateral_msg,
+        query_overseer_unlock_and_withdraw_collateral_msg,
+    },
+    state::{read_collateral_info, CONFIG},
+};
+
+impl Operation for PositionOperations {
+    fn build(self, deps: Deps, env: Env) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            PositionOperations::Leverage {
+                executor_addr,
+                collateral,
+                initial_amount,
+                target_ltv,
+                max_iterations,
+                swap_route,
+                max_slippage,
+            } => query_leverage_msgs(
+                deps,
+                env,
+                executor_addr,
+                collateral,
+                initial_amount,
+                target_ltv,
+                max_iterations,
+                swap_route,
+                max_slippage,
+            ),
+            PositionOperations::Deleverage {
+                executor_addr,
+                collateral,
+                target_ltv,
+                max_iterations,
+                swap_route,
+                max_slippage,
+            } => query_deleverage_msgs(
+                deps,
+                env,
+                executor_addr,
+                collateral,
+                target_ltv,
+                max_iterations,
+                swap_route,
+                max_slippage,
+            ),
+        }
+    }
+}
+
+/// Slippage allowed on each swap when `max_slippage` is unset
+const DEFAULT_MAX_SLIPPAGE_PERMILLE: u64 = 5;
+
+/// Addresses and prices shared by every iteration of a loop.
+struct LoopContext {
+    stable_addr: Addr,
+    router: Addr,
+    /// Stable token per collateral token
+    price: Decimal256,
+    target_ltv: Decimal256,
+    /// Sold so far along the route and the simulated return of it, every
+    /// leg is priced after the impact of the legs before it
+    offered: Uint128,
+    simulated: Uint128,
+}
+
+impl LoopContext {
+    fn load(deps: Deps, collateral: &Addr, target_ltv: Decimal) -> StdResult<Self> {
+        let config = CONFIG.load(deps.storage)?;
+        let stable_addr = deps.api.addr_humanize(&config.stable_addr)?;
+        let router = load_router(deps)?;
+        let oracle = load_oracle(deps)?;
+
+        let target_ltv = Decimal256::from(target_ltv);
+        let max_ltv = query_max_ltv(
+            &deps.querier,
+            deps.api.addr_humanize(&config.overseer)?,
+            collateral.clone(),
+        )?;
+        if target_ltv.is_zero() || target_ltv >= max_ltv {
+            return Err(StdError::generic_err(format!(
+                "target_ltv must be positive and below the max ltv {}",
+                max_ltv
+            )));
+        }
+
+        let price = query_price(
+            &deps.querier,
+            oracle,
+            collateral.to_string(),
+            stable_addr.to_string(),
+        )?;
+        if price.is_zero() {
+            return Err(StdError::generic_err(format!(
+                "{} has no price",
+                collateral
+            )));
+        }
+
+        Ok(LoopContext {
+            stable_addr,
+            router,
+            price,
+            target_ltv,
+            offered: Uint128::zero(),
+            simulated: Uint128::zero(),
+        })
+    }
+
+    /// Swap `amount` more of `token`, returns the message and the output it
+    /// guarantees on top of the previous legs.
+    fn swap_msg(
+        &mut self,
+        deps: Deps,
+        token: &Addr,
+        amount: Uint128,
+        route: &[SwapOperation],
+        max_slippage: Option<Decimal>,
+    ) -> StdResult<(CosmosMsg, Uint128)> {
+        let offered = self.offered.checked_add(amount)?;
+        let simulated =
+            simulate_swap_operations(&deps.querier, self.router.clone(), offered, route.to_vec())?;
+        let return_amount = simulated.saturating_sub(self.simulated);
+        self.offered = offered;
+        self.simulated = simulated;
+
+        let minimum_receive = minimum_receive(return_amount, max_slippage)?;
+        let msg = swap_msg(&self.router, token, amount, route, minimum_receive)?;
+        Ok((msg, minimum_receive))
+    }
+}
+
+pub fn load_router(deps: Deps) -> StdResult<Addr> {
+    let config = CONFIG.load(deps.storage)?;
+    deps.api.addr_humanize(
+        &config
+            .oraiswap_router
+            .ok_or_else(|| StdError::generic_err("Oraiswap router is not configured"))?,
+    )
+}
+
+pub fn load_oracle(deps: Deps) -> StdResult<Addr> {
+    let config = CONFIG.load(deps.storage)?;
+    deps.api.addr_humanize(
+        &config
+            .oracle
+            .ok_or_else(|| StdError::generic_err("Oracle is not configured"))?,
+    )
+}
+
+/// Sell `amount` of cw20 `token` along `route` through `router`, returns the
+/// message and the output it guarantees.
+pub fn query_swap_msg(
+    deps: Deps,
+    router: &Addr,
+    token: &Addr,
+    amount: Uint128,
+    route: &[SwapOperation],
+    max_slippage: Option<Decimal>,
+) -> StdResult<(CosmosMsg, Uint128)> {
+    let return_amount =
+        simulate_swap_operations(&deps.querier, router.clone(), amount, route.to_vec())?;
+    let minimum_receive = minimum_receive(return_amount, max_slippage)?;
+    let msg = swap_msg(router, token, amount, route, minimum_receive)?;
+    Ok((msg, minimum_receive))
+}
+
+/// `return_amount` less `max_slippage`, `DEFAULT_MAX_SLIPPAGE_PERMILLE` when
+/// unset: the pools move between building and executing the recipe.
+fn minimum_receive(return_amount: Uint128, max_slippage: Option<Decimal>) -> StdResult<Uint128> {
+    let max_slippage =
+        max_slippage.unwrap_or_else(|| Decimal::permille(DEFAULT_MAX_SLIPPAGE_PERMILLE));
+    if max_slippage > Decimal::one() {
+        return Err(StdError::generic_err("max_slippage must not exceed 1"));
+    }
+    Ok(return_amount * (Decimal::one() - max_slippage))
+}
+
+fn swap_msg(
+    router: &Addr,
+    token: &Addr,
+    amount: Uint128,
+    route: &[SwapOperation],
+    minimum_receive: Uint128,
+) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: router.to_string(),
+            amount,
+            msg: to_binary(&OraiswapExecuteMsg::ExecuteSwapOperations {
+                operations: route.to_vec(),
+                minimum_receive: Some(minimum_receive),
+                to: None,
+            })?,
+        })?,
+        funds: vec![],
+    }))
+}
+
+/// Loan of `executor_addr` and its `collateral` locked in the overseer.
+fn query_position(
+    deps: Deps,
+    env: &Env,
+    executor_addr: &Addr,
+    collateral: &Addr,
+) -> StdResult<(Uint256, Uint256)> {
+    let config = CONFIG.load(deps.storage)?;
+    let collateral_info = read_collateral_info(
+        deps.storage,
+        &deps.api.addr_canonicalize(collateral.as_str())?,
+    )?;
+    let loan = query_loan_amount(
+        &deps.querier,
+        deps.api.addr_humanize(&config.market)?,
+        executor_addr.clone(),
+        env.block.height,
+    )?;
+    let borrower_info = query_custody_borrower(
+        &deps.querier,
+        deps.api.addr_humanize(&collateral_info.custody_contract)?,
+        executor_addr.clone(),
+    )?;
+    Ok((loan, borrower_info.balance - borrower_info.spendable))
+}
+
+pub fn assert_route(route: &[SwapOperation], offer: &Addr, ask: &Addr) -> StdResult<()> {
+    let offer_info = AssetInfo::Token {
+        contract_addr: offer.clone(),
+    };
+    let ask_info = AssetInfo::Token {
+        contract_addr: ask.clone(),
+    };
+    match (route.first(), route.last()) {
+        (Some(first), Some(last))
+            if first.get_offer_asset_info() == offer_info
+                && last.get_target_asset_info() == ask_info =>
+        {
+            Ok(())
+        }
+        _ => Err(StdError::generic_err(format!(
+            "swap_route must go from {} to {}",
+            offer, ask
+        ))),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn query_leverage_msgs(
+    deps: Deps,
+    env: Env,
+    executor_addr: Addr,
+    collateral: Addr,
+    initial_amount: Option<AmountSpec>,
+    target_ltv: Decimal,
+    max_iterations: u8,
+    swap_route: Vec<SwapOperation>,
+    max_slippage: Option<Decimal>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut context = LoopContext::load(deps, &collateral, target_ltv)?;
+    assert_route(&swap_route, &context.stable_addr, &collateral)?;
+    let (mut loan, mut locked) = query_position(deps, &env, &executor_addr, &collateral)?;
+
+    // an existing position is levered without a new deposit
+    let deposit = match initial_amount {
+        None if !locked.is_zero() => {
+            query_token_balance(&deps.querier, collateral.clone(), executor_addr.clone())?
+        }
+        initial_amount => AmountSource::token_balance(
+            initial_amount,
+            collateral.clone(),
+            Some(executor_addr.clone()),
+        )?
+        .resolve(&deps.querier)?,
+    };
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !deposit.is_zero() {
+        messages.extend(query_overseer_provide_and_lock_collateral_msg(
+            deps,
+            env.clone(),
+            executor_addr.clone(),
+            executor_addr.clone(),
+            collateral.clone(),
+            Some(AmountSpec::Exact(deposit)),
+        )?);
+        locked += Uint256::from(deposit);
+    }
+    if locked.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "No {} locked or deposited to leverage",
+            collateral
+        )));
+    }
+
+    for _ in 0..max_iterations {
+        // borrow up to target_ltv of the collateral locked so far, counting the
+        // whole loan against it
+        let borrow_amount =
+            Uint128::try_from((locked * context.price * context.target_ltv).saturating_sub(loan))?;
+        if borrow_amount.is_zero() {
+            break;
+        }
+        let (swap_msg, received) = context.swap_msg(
+            deps,
+            &context.stable_addr.clone(),
+            borrow_amount,
+            &swap_route,
+            max_slippage,
+        )?;
+        if received.is_zero() {
+            break;
+        }
+
+        messages.extend(query_market_borrow_stable_msgs(
+            deps,
+            env.clone(),
+            borrow_amount.into(),
+            None,
+        )?);
+        messages.push(swap_msg);
+        // the guaranteed output, anything the swap returns above it stays
+        // with the executor
+        messages.extend(query_overseer_provide_and_lock_collateral_msg(
+            deps,
+            env.clone(),
+            executor_addr.clone(),
+            executor_addr.clone(),
+            collateral.clone(),
+            Some(AmountSpec::Exact(received)),
+        )?);
+        loan += Uint256::from(borrow_amount);
+        locked += Uint256::from(received);
+    }
+
+    Ok(messages)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn query_deleverage_msgs(
+    deps: Deps,
+    env: Env,
+    executor_addr: Addr,
+    collateral: Addr,
+    target_ltv: Decimal,
+    max_iterations: u8,
+    swap_route: Vec<SwapOperation>,
+    max_slippage: Option<Decimal>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut context = LoopContext::load(deps, &collateral, target_ltv)?;
+    assert_route(&swap_route, &collateral, &context.stable_addr)?;
+    let (mut loan, mut locked) = query_position(deps, &env, &executor_addr, &collateral)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for _ in 0..max_iterations {
+        if loan.is_zero() {
+            break;
+        }
+        // collateral still needed to keep the remaining loan at target_ltv
+        let required = loan * (Decimal256::one() / (context.price * context.target_ltv));
+        let unlock_amount = Uint128::try_from(locked.saturating_sub(required))?;
+        if unlock_amount.is_zero() {
+            if messages.is_empty() {
+                return Err(StdError::generic_err(
+                    "Position is above target_ltv, no collateral can be unlocked",
+                ));
+            }
+            break;
+        }
+        let (swap_msg, received) =
+            context.swap_msg(deps, &collateral, unlock_amount, &swap_route, max_slippage)?;
+        let repay_amount = std::cmp::min(Uint256::from(received), loan);
+
+        messages.extend(query_overseer_unlock_and_withdraw_collateral_msg(
+            deps,
+            env.clone(),
+            Some(executor_addr.clone()),
+            collateral.clone(),
+            Some(AmountSpec::Exact(unlock_amount)),
+        )?);
+        messages.push(swap_msg);
+        if !repay_amount.is_zero() {
+            messages.extend(query_market_repay_stable_msgs(
+                deps,
+                env.clone(),
+                Uint128::try_from(repay_amount)?,
+                executor_addr.clone(),
+                executor_addr.clone(),
+            )?);
+        }
+        loan -= repay_amount;
+        locked -= Uint256::from(unlock_amount);
+    }
+
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{mock_deps, route, MockPosition, COLLATERAL, EXECUTOR, STABLE};
+    use cosmwasm_std::testing::mock_env;
+
+    #[test]
+    fn minimum_receive_defaults_to_half_a_percent() {
+        assert_eq!(
+            minimum_receive(Uint128::new(1000), None).unwrap(),
+            Uint128::new(995)
+        );
+        assert_eq!(
+            minimum_receive(Uint128::new(1000), Some(Decimal::percent(2))).unwrap(),
+            Uint128::new(980)
+        );
+        assert!(minimum_receive(Uint128::new(1000), Some(Decimal::percent(101))).is_err());
+    }
+
+    fn leverage(
+        position: MockPosition,
+        initial_amount: Option<AmountSpec>,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        let deps = mock_deps(position);
+        query_leverage_msgs(
+            deps.as_ref(),
+            mock_env(),
+            Addr::unchecked(EXECUTOR),
+            Addr::unchecked(COLLATERAL),
+            initial_amount,
+            Decimal::percent(50),
+            1,
+            route(STABLE, COLLATERAL),
+            None,
+        )
+    }
+
+    #[test]
+    fn leverage_existing_position_without_deposit() {
+        let position = MockPosition {
+            locked: 1000,
+            ..MockPosition::default()
+        };
+        for initial_amount in [None, Some(AmountSpec::Exact(Uint128::zero()))] {
+            let messages = leverage(position, initial_amount).unwrap();
+            // borrow, swap, then deposit and lock the swapped collateral
+            assert_eq!(messages.len(), 4);
+            assert_eq!(
+                messages[0],
+                query_market_borrow_stable_msgs(
+                    mock_deps(position).as_ref(),
+                    mock_env(),
+                    Uint256::from(500u128),
+                    None
+                )
+                .unwrap()[0]
+            );
+        }
+
+        let err = leverage(
+            MockPosition::default(),
+            Some(AmountSpec::Exact(Uint128::zero())),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("No collateral locked or deposited to leverage")
+        );
+    }
+}
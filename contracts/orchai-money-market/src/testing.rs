@@ -0,0 +1,145 @@
+use cosmwasm_std::testing::{mock_dependencies, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    from_slice, to_binary, Addr, Api, Binary, ContractResult, Decimal256, OwnedDeps, StdResult,
+    SystemResult, Uint128, Uint256, WasmQuery,
+};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use oraidex::{
+    asset::AssetInfo,
+    router::{OraiswapQueryMsg, SimulateSwapOperationsResponse, SwapOperation},
+};
+use orchai::{
+    custody::{BorrowerResponse, CustodyQueryMsg},
+    market::{BorrowerInfoResponse, MarketQueryMsg},
+    oracle::{OracleQueryMsg, PriceResponse},
+    overseer::{BorrowLimitResponse, OverseerQueryMsg, WhitelistResponse, WhitelistResponseElem},
+};
+
+use crate::state::{CollateralInfo, Config, COLLATERALS, CONFIG};
+
+pub const EXECUTOR: &str = "executor";
+pub const STABLE: &str = "stable";
+pub const COLLATERAL: &str = "collateral";
+pub const CUSTODY: &str = "custody";
+pub const MAX_LTV_PERCENT: u64 = 60;
+
+/// Position of `EXECUTOR`, one collateral priced at 1 stable, swapped 1:1.
+#[derive(Clone, Copy, Default)]
+pub struct MockPosition {
+    pub stable_balance: u128,
+    pub collateral_balance: u128,
+    pub loan: u128,
+    pub locked: u128,
+}
+
+pub fn route(offer: &str, ask: &str) -> Vec<SwapOperation> {
+    vec![SwapOperation::OraiSwap {
+        offer_asset_info: AssetInfo::Token {
+            contract_addr: Addr::unchecked(offer),
+        },
+        ask_asset_info: AssetInfo::Token {
+            contract_addr: Addr::unchecked(ask),
+        },
+    }]
+}
+
+pub fn mock_deps(position: MockPosition) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    let api = deps.api;
+    let config = Config {
+        owner: api.addr_canonicalize("owner").unwrap(),
+        overseer: api.addr_canonicalize("overseer").unwrap(),
+        market: api.addr_canonicalize("market").unwrap(),
+        liquidation: api.addr_canonicalize("liquidation").unwrap(),
+        stable_addr: api.addr_canonicalize(STABLE).unwrap(),
+        a_stable_contract: api.addr_canonicalize("astable").unwrap(),
+        a_stable_contract_reward: api.addr_canonicalize("astable_reward").unwrap(),
+        oraiswap_router: Some(api.addr_canonicalize("router").unwrap()),
+        oracle: Some(api.addr_canonicalize("oracle").unwrap()),
+    };
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    let collateral_raw = api.addr_canonicalize(COLLATERAL).unwrap();
+    COLLATERALS
+        .save(
+            deps.as_mut().storage,
+            collateral_raw.as_slice(),
+            &CollateralInfo {
+                collateral: collateral_raw.clone(),
+                custody_contract: api.addr_canonicalize(CUSTODY).unwrap(),
+            },
+        )
+        .unwrap();
+
+    deps.querier.update_wasm(move |query| match query {
+        WasmQuery::Smart { contract_addr, msg } => SystemResult::Ok(ContractResult::Ok(
+            query_contract(position, contract_addr, msg).unwrap(),
+        )),
+        _ => panic!("unexpected query"),
+    });
+    deps
+}
+
+fn query_contract(position: MockPosition, contract_addr: &str, msg: &Binary) -> StdResult<Binary> {
+    let max_ltv = Decimal256::percent(MAX_LTV_PERCENT);
+    match contract_addr {
+        STABLE | COLLATERAL => {
+            let _: Cw20QueryMsg = from_slice(msg)?;
+            let balance = if contract_addr == STABLE {
+                position.stable_balance
+            } else {
+                position.collateral_balance
+            };
+            to_binary(&BalanceResponse {
+                balance: Uint128::new(balance),
+            })
+        }
+        "market" => {
+            let MarketQueryMsg::BorrowerInfo { borrower, .. } = from_slice(msg)?;
+            to_binary(&BorrowerInfoResponse {
+                borrower,
+                interest_index: Decimal256::one(),
+                reward_index: Decimal256::zero(),
+                loan_amount: Uint256::from(position.loan),
+                pending_rewards: Decimal256::zero(),
+            })
+        }
+        "overseer" => match from_slice(msg)? {
+            OverseerQueryMsg::Whitelist { .. } => to_binary(&WhitelistResponse {
+                elems: vec![WhitelistResponseElem {
+                    name: "collateral".to_string(),
+                    symbol: "COL".to_string(),
+                    max_ltv,
+                    custody_contract: CUSTODY.to_string(),
+                    collateral_token: COLLATERAL.to_string(),
+                }],
+            }),
+            OverseerQueryMsg::BorrowLimit { borrower, .. } => to_binary(&BorrowLimitResponse {
+                borrower,
+                borrow_limit: Uint256::from(position.locked) * max_ltv,
+            }),
+        },
+        CUSTODY => {
+            let CustodyQueryMsg::Borrower { address } = from_slice(msg)?;
+            to_binary(&BorrowerResponse {
+                borrower: address,
+                balance: Uint256::from(position.locked),
+                spendable: Uint256::zero(),
+            })
+        }
+        "oracle" => {
+            let OracleQueryMsg::Price { .. } = from_slice(msg)?;
+            to_binary(&PriceResponse {
+                rate: Decimal256::one(),
+                last_updated_base: 0,
+                last_updated_quote: 0,
+            })
+        }
+        "router" => {
+            let OraiswapQueryMsg::SimulateSwapOperations { offer_amount, .. } = from_slice(msg)?;
+            to_binary(&SimulateSwapOperationsResponse {
+                amount: offer_amount,
+            })
+        }
+        _ => panic!("unexpected contract {}", contract_addr),
+    }
+}
@@ -0,0 +1,12 @@
+use cosmwasm_schema::write_api;
+
+use recipe_router::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+
+fn main() {
+    write_api!(
+        instantiate: InstantiateMsg,
+        query: QueryMsg,
+        execute: ExecuteMsg,
+        migrate: MigrateMsg
+    )
+}
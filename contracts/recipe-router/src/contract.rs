@@ -0,0 +1,347 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Api, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdError, StdResult,
+};
+use cw_storage_plus::Bound;
+use operations_core::{
+    instantiate_entry_point, migrate_entry_point,
+    ownership::{assert_owner, update_config},
+};
+
+use crate::{
+    error::ContractError,
+    msg::{
+        AdapterQueryMsg, AdapterResponse, AdaptersResponse, ConfigResponse, ExecuteMsg,
+        InstantiateMsg, MigrateMsg, QueryMsg, RecipeStep,
+    },
+    state::{Config, ADAPTERS, CONFIG},
+};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+instantiate_entry_point!(InstantiateMsg, CONFIG, config_from_msg);
+
+fn config_from_msg(api: &dyn Api, msg: InstantiateMsg) -> StdResult<Config> {
+    Ok(Config {
+        owner: api.addr_canonicalize(msg.owner.as_str())?,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateConfig { owner } => execute_update_config(deps, info, owner),
+        ExecuteMsg::RegisterAdapter {
+            protocol,
+            contract_addr,
+        } => execute_register_adapter(deps, info, protocol, contract_addr),
+        ExecuteMsg::DeregisterAdapter { protocol } => {
+            execute_deregister_adapter(deps, info, protocol)
+        }
+    }
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+) -> Result<Response, ContractError> {
+    Ok(update_config(deps, &info, &CONFIG, owner, |_, _| Ok(()))?)
+}
+
+pub fn execute_register_adapter(
+    deps: DepsMut,
+    info: MessageInfo,
+    protocol: String,
+    contract_addr: Addr,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_owner(deps.api, &config, &info.sender)?;
+
+    let contract_raw = deps.api.addr_canonicalize(contract_addr.as_str())?;
+    ADAPTERS.save(deps.storage, &protocol, &contract_raw)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "register_adapter"),
+        ("protocol", protocol.as_str()),
+        ("contract_addr", contract_addr.as_str()),
+    ]))
+}
+
+pub fn execute_deregister_adapter(
+    deps: DepsMut,
+    info: MessageInfo,
+    protocol: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_owner(deps.api, &config, &info.sender)?;
+
+    if !ADAPTERS.has(deps.storage, &protocol) {
+        return Err(ContractError::AdapterNotFound { protocol });
+    }
+    ADAPTERS.remove(deps.storage, &protocol);
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "deregister_adapter"),
+        ("protocol", protocol.as_str()),
+    ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Messages { steps } => to_binary(&query_messages(deps, steps)?),
+        QueryMsg::Adapter { protocol } => to_binary(&query_adapter(deps, protocol)?),
+        QueryMsg::Adapters { start_after, limit } => {
+            to_binary(&query_adapters(deps, start_after, limit)?)
+        }
+    }
+}
+
+fn query_messages(deps: Deps, steps: Vec<RecipeStep>) -> StdResult<Vec<CosmosMsg>> {
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for (index, step) in steps.into_iter().enumerate() {
+        let adapter = query_adapter(deps, step.protocol)
+            .map_err(|err| StdError::generic_err(format!("Step {}: {}", index, err)))?;
+        let step_messages: Vec<CosmosMsg> = deps
+            .querier
+            .query_wasm_smart(
+                adapter.contract_addr,
+                &AdapterQueryMsg::Messages {
+                    msg: step.operation,
+                },
+            )
+            .map_err(|err| {
+                StdError::generic_err(format!("Step {} ({}): {}", index, adapter.protocol, err))
+            })?;
+        messages.extend(step_messages);
+    }
+    Ok(messages)
+}
+
+fn query_adapter(deps: Deps, protocol: String) -> StdResult<AdapterResponse> {
+    let contract_raw = ADAPTERS
+        .may_load(deps.storage, &protocol)?
+        .ok_or_else(|| StdError::not_found(format!("Adapter for protocol {}", protocol)))?;
+    Ok(AdapterResponse {
+        protocol,
+        contract_addr: deps.api.addr_humanize(&contract_raw)?,
+    })
+}
+
+fn query_adapters(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AdaptersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let adapters = ADAPTERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (protocol, contract_raw) = item?;
+            Ok(AdapterResponse {
+                protocol,
+                contract_addr: deps.api.addr_humanize(&contract_raw)?,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(AdaptersResponse { adapters })
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: deps.api.addr_humanize(&config.owner)?,
+    })
+}
+
+migrate_entry_point!(MigrateMsg);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{from_binary, BankMsg, ContractResult, OwnedDeps, SystemResult, WasmQuery};
+    use operations_core::OperationsError;
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {
+                owner: Addr::unchecked("owner"),
+            },
+        )
+        .unwrap();
+        for protocol in ["dex", "market", "staking"] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                ExecuteMsg::RegisterAdapter {
+                    protocol: protocol.to_string(),
+                    contract_addr: Addr::unchecked(format!("{}_adapter", protocol)),
+                },
+            )
+            .unwrap();
+        }
+
+        // every adapter returns one bank send tagged with its operation, the
+        // market adapter fails
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                let AdapterQueryMsg::Messages { msg } = from_binary(msg).unwrap();
+                if contract_addr == "market_adapter" {
+                    return SystemResult::Ok(ContractResult::Err("boom".to_string()));
+                }
+                let denom: String = from_binary(&msg).unwrap();
+                let messages: Vec<CosmosMsg> = vec![BankMsg::Send {
+                    to_address: contract_addr.clone(),
+                    amount: cosmwasm_std::coins(1, denom),
+                }
+                .into()];
+                SystemResult::Ok(ContractResult::Ok(to_binary(&messages).unwrap()))
+            }
+            _ => panic!("unexpected query"),
+        });
+        deps
+    }
+
+    fn step(protocol: &str, denom: &str) -> RecipeStep {
+        RecipeStep {
+            protocol: protocol.to_string(),
+            operation: to_binary(denom).unwrap(),
+        }
+    }
+
+    #[test]
+    fn only_owner_manages_adapters() {
+        let mut deps = setup();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("stranger", &[]),
+            ExecuteMsg::RegisterAdapter {
+                protocol: "dex".to_string(),
+                contract_addr: Addr::unchecked("other"),
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::Operations(OperationsError::Unauthorized {})
+        ));
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("stranger", &[]),
+            ExecuteMsg::DeregisterAdapter {
+                protocol: "dex".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::Operations(OperationsError::Unauthorized {})
+        ));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::DeregisterAdapter {
+                protocol: "dex".to_string(),
+            },
+        )
+        .unwrap();
+        assert!(query_adapter(deps.as_ref(), "dex".to_string()).is_err());
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::DeregisterAdapter {
+                protocol: "dex".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::AdapterNotFound { .. }));
+    }
+
+    #[test]
+    fn adapters_paginate_by_protocol() {
+        let deps = setup();
+        let protocols = |start_after: Option<&str>, limit| {
+            query_adapters(deps.as_ref(), start_after.map(String::from), Some(limit))
+                .unwrap()
+                .adapters
+                .into_iter()
+                .map(|adapter| adapter.protocol)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(protocols(None, 2), vec!["dex", "market"]);
+        assert_eq!(protocols(Some("market"), 2), vec!["staking"]);
+        assert!(protocols(Some("staking"), 2).is_empty());
+    }
+
+    #[test]
+    fn messages_follow_step_order() {
+        let deps = setup();
+        let messages = query_messages(
+            deps.as_ref(),
+            vec![step("staking", "a"), step("dex", "b"), step("staking", "c")],
+        )
+        .unwrap();
+        let sends: Vec<(String, String)> = messages
+            .into_iter()
+            .map(|msg| match msg {
+                CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                    (to_address, amount[0].denom.clone())
+                }
+                _ => panic!("unexpected message"),
+            })
+            .collect();
+        assert_eq!(
+            sends,
+            vec![
+                ("staking_adapter".to_string(), "a".to_string()),
+                ("dex_adapter".to_string(), "b".to_string()),
+                ("staking_adapter".to_string(), "c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn step_errors_name_the_step() {
+        let deps = setup();
+        let err = query_messages(deps.as_ref(), vec![step("dex", "a"), step("lending", "b")])
+            .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("Step 1: Adapter for protocol lending not found")
+        );
+
+        let err =
+            query_messages(deps.as_ref(), vec![step("dex", "a"), step("market", "b")]).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Generic error: Step 1 (market): "));
+        assert!(err.to_string().contains("boom"));
+    }
+}
@@ -0,0 +1,17 @@
+use cosmwasm_std::StdError;
+use operations_core::OperationsError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Operations(#[from] OperationsError),
+
+    #[error("Adapter for protocol {protocol} is not registered")]
+    AdapterNotFound { protocol: String },
+    // Add any other custom errors you like here.
+    // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
+}
@@ -0,0 +1,70 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Binary, CosmosMsg};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub owner: Addr,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    UpdateConfig {
+        owner: Option<Addr>,
+    },
+    RegisterAdapter {
+        protocol: String,
+        contract_addr: Addr,
+    },
+    DeregisterAdapter {
+        protocol: String,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Resolves every step through its protocol adapter and returns the messages in step order
+    #[returns(Vec<CosmosMsg>)]
+    Messages { steps: Vec<RecipeStep> },
+    #[returns(AdapterResponse)]
+    Adapter { protocol: String },
+    #[returns(AdaptersResponse)]
+    Adapters {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(ConfigResponse)]
+    Config {},
+}
+
+#[cw_serde]
+pub struct RecipeStep {
+    pub protocol: String,
+    /// Operation json understood by the adapter registered for `protocol`
+    pub operation: Binary,
+}
+
+/// Query interface shared by every operations contract
+#[cw_serde]
+pub enum AdapterQueryMsg {
+    Messages { msg: Binary },
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub owner: Addr,
+}
+
+#[cw_serde]
+pub struct AdapterResponse {
+    pub protocol: String,
+    pub contract_addr: Addr,
+}
+
+#[cw_serde]
+pub struct AdaptersResponse {
+    pub adapters: Vec<AdapterResponse>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
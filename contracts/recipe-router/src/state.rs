@@ -0,0 +1,17 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::CanonicalAddr;
+use cw_storage_plus::{Item, Map};
+use operations_core::impl_owned_config;
+
+// put the length bytes at the first for compatibility with legacy singleton store
+pub const CONFIG: Item<Config> = Item::new("\u{0}\u{6}config");
+
+/// Operations contract registered for each protocol name
+pub const ADAPTERS: Map<&str, CanonicalAddr> = Map::new("adapters");
+
+#[cw_serde]
+pub struct Config {
+    pub owner: CanonicalAddr,
+}
+
+impl_owned_config!(Config);
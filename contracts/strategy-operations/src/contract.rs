@@ -1,31 +1,23 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{
-    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+use cosmwasm_std::{Addr, Api, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use operations_core::{
+    instantiate_entry_point, migrate_entry_point, ownership::update_config, query_entry_point,
 };
 
 use crate::{
     error::ContractError,
     msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StrategyOperations},
     state::{Config, CONFIG},
-    strategy::query_verify_strategy_msg,
 };
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    msg: InstantiateMsg,
-) -> Result<Response, ContractError> {
-    CONFIG.save(
-        deps.storage,
-        &Config {
-            owner: deps.api.addr_canonicalize(msg.owner.as_str())?,
-            strategy_contract: deps.api.addr_canonicalize(msg.strategy_contract.as_str())?,
-        },
-    )?;
-    Ok(Response::default())
+instantiate_entry_point!(InstantiateMsg, CONFIG, config_from_msg);
+
+fn config_from_msg(api: &dyn Api, msg: InstantiateMsg) -> StdResult<Config> {
+    Ok(Config {
+        owner: api.addr_canonicalize(msg.owner.as_str())?,
+        strategy_contract: api.addr_canonicalize(msg.strategy_contract.as_str())?,
+    })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -49,39 +41,22 @@ pub fn execute_update_config(
     owner: Option<Addr>,
     strategy_contract: Option<Addr>,
 ) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
-    if deps.api.addr_humanize(&config.owner)? != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
-
-    if let Some(owner) = owner {
-        config.owner = deps.api.addr_canonicalize(owner.as_str())?;
-    }
-
-    if let Some(strategy_contract) = strategy_contract {
-        config.strategy_contract = deps.api.addr_canonicalize(strategy_contract.as_str())?;
-    }
-
-    CONFIG.save(deps.storage, &config)?;
-
-    Ok(Response::new().add_attribute("action", "update_config"))
-}
-
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Config {} => to_binary(&query_config(deps)?),
-        QueryMsg::Messages { msg } => match from_binary(&msg) {
-            Ok(StrategyOperations::VerifyStrategy { creator, id }) => {
-                to_binary(&query_verify_strategy_msg(deps, creator, id)?)
+    Ok(update_config(
+        deps,
+        &info,
+        &CONFIG,
+        owner,
+        |api, config| {
+            if let Some(strategy_contract) = strategy_contract {
+                config.strategy_contract = api.addr_canonicalize(strategy_contract.as_str())?;
             }
-            _ => Err(cosmwasm_std::StdError::NotFound {
-                kind: "Operations not found".to_string(),
-            }),
+            Ok(())
         },
-    }
+    )?)
 }
 
+query_entry_point!(QueryMsg, StrategyOperations, query_config);
+
 fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
     Ok(ConfigResponse {
@@ -90,7 +65,4 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     })
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
-}
+migrate_entry_point!(MigrateMsg);
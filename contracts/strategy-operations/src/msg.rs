@@ -1,5 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Binary, CosmosMsg};
+use operations_core::operation::BatchMessagesResponse;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -20,6 +21,8 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(Vec<CosmosMsg>)]
     Messages { msg: Binary },
+    #[returns(BatchMessagesResponse)]
+    BatchMessages { msgs: Vec<Binary> },
     #[returns(ConfigResponse)]
     Config {},
 }
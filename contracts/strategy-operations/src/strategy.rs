@@ -1,7 +1,18 @@
-use cosmwasm_std::{to_binary, Addr, CosmosMsg, Deps, StdResult};
+use cosmwasm_std::{to_binary, Addr, CosmosMsg, Deps, Env, StdResult};
+use operations_core::Operation;
 use strategy::strategy::StrategyExecuteMsg;
 
-use crate::state::CONFIG;
+use crate::{msg::StrategyOperations, state::CONFIG};
+
+impl Operation for StrategyOperations {
+    fn build(self, deps: Deps, _env: Env) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            StrategyOperations::VerifyStrategy { creator, id } => {
+                query_verify_strategy_msg(deps, creator, id)
+            }
+        }
+    }
+}
 
 pub fn query_verify_strategy_msg(
     deps: Deps,
@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use operations_core::OperationsError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,8 +7,8 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
-    #[error("Unauthorized")]
-    Unauthorized {},
+    #[error("{0}")]
+    Operations(#[from] OperationsError),
     // Add any other custom errors you like here.
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
 }
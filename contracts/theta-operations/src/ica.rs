@@ -9,8 +9,70 @@ use cosmos_sdk_proto::{
     Any,
 };
 use cosmwasm_std::{Coin, CosmosMsg, Deps, Env, IbcMsg, IbcTimeout, StdResult, Timestamp, Uint128};
+use operations_core::Operation;
 
-use crate::state::CONFIG;
+use crate::{msg::IcaOperations, state::CONFIG};
+
+impl Operation for IcaOperations {
+    fn build(self, deps: Deps, env: Env) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            IcaOperations::TransferToHostChain {
+                executor_addr,
+                denom,
+                amount,
+                receiver,
+                source_port,
+                source_channel,
+            } => query_transfer_to_host_chain_msg(
+                deps,
+                env,
+                executor_addr,
+                denom,
+                amount,
+                receiver,
+                source_port,
+                source_channel,
+            ),
+            IcaOperations::TransferFromHostChain {
+                executor_addr,
+                denom,
+                amount,
+                sender,
+                receiver,
+                source_port,
+                source_channel,
+            } => query_transfer_from_host_chain_msg(
+                deps,
+                env,
+                executor_addr,
+                denom,
+                amount,
+                sender,
+                receiver,
+                source_port,
+                source_channel,
+            ),
+            IcaOperations::DelegateOnHostChain {
+                executor_addr,
+                delegator,
+                validator,
+                denom,
+                amount,
+            } => query_delegate_on_host_chain(
+                deps,
+                env,
+                executor_addr,
+                delegator,
+                validator,
+                denom,
+                amount,
+            ),
+            IcaOperations::RegisterInterchainAccount { executor_addr } => {
+                query_register_interchain_account_msg(deps, executor_addr)
+            }
+        }
+    }
+}
 
 pub fn query_register_interchain_account_msg(
     deps: Deps,
@@ -1,5 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Binary, CosmosMsg, Uint128};
+use operations_core::operation::BatchMessagesResponse;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -22,6 +23,8 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(Vec<CosmosMsg>)]
     Messages { msg: Binary },
+    #[returns(BatchMessagesResponse)]
+    BatchMessages { msgs: Vec<Binary> },
     #[returns(ConfigResponse)]
     Config {},
 }
@@ -1,6 +1,76 @@
 use std::vec;
 
-use cosmwasm_std::{Addr, Coin, CosmosMsg, Deps, StdResult, Uint128};
+use cosmwasm_std::{
+    Addr, Coin, CosmosMsg, Decimal, Deps, DistributionMsg, Env, GovMsg, StdError, StdResult,
+    Uint128, VoteOption, WeightedVoteOption,
+};
+use operations_core::{AmountSource, AmountSpec, Operation, OperationsError};
+
+use crate::msg::ChainOperations;
+
+impl Operation for ChainOperations {
+    fn build(self, deps: Deps, _env: Env) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            ChainOperations::ChainDelegate {
+                executor_addr,
+                validator,
+                denom,
+                amount,
+            } => query_delegate_msg(deps, executor_addr, validator, denom, amount),
+            ChainOperations::ChainUndelegate {
+                executor_addr,
+                validator,
+                denom,
+                amount,
+            } => query_undelegate_msg(deps, executor_addr, validator, denom, amount),
+            ChainOperations::DelegateSplit {
+                executor_addr,
+                denom,
+                amount,
+                validators,
+            } => query_delegate_split_msg(deps, executor_addr, denom, amount, validators),
+            ChainOperations::UndelegateProportional {
+                executor_addr,
+                denom,
+                amount,
+            } => query_undelegate_proportional_msg(deps, executor_addr, denom, amount),
+            ChainOperations::ChainRedelegate {
+                executor_addr,
+                src_validator,
+                dst_validator,
+                denom,
+                amount,
+            } => query_redelegate_msg(
+                deps,
+                executor_addr,
+                src_validator,
+                dst_validator,
+                denom,
+                amount,
+            ),
+            ChainOperations::ChainClaimRewards {
+                executor_addr,
+                validator,
+            } => query_claim_rewards_msg(deps, executor_addr, validator),
+            ChainOperations::ChainSetWithdrawAddress { address } => {
+                query_set_withdraw_address_msg(address)
+            }
+            ChainOperations::ChainClaimAndRestake {
+                executor_addr,
+                validator,
+                denom,
+            } => query_claim_and_restake_msg(deps, executor_addr, validator, denom),
+            ChainOperations::Vote {
+                proposal_id,
+                option,
+            } => query_vote_msg(proposal_id, option),
+            ChainOperations::WeightedVote {
+                proposal_id,
+                options,
+            } => query_weighted_vote_msg(proposal_id, options),
+        }
+    }
+}
 
 pub fn query_delegate_msg(
     deps: Deps,
@@ -9,12 +79,12 @@ pub fn query_delegate_msg(
     denom: String,
     amount: Option<Uint128>,
 ) -> StdResult<Vec<CosmosMsg>> {
-    let amount = amount.unwrap_or_else(|| {
-        deps.querier
-            .query_balance(executor_addr.to_string(), denom.clone())
-            .unwrap()
-            .amount
-    });
+    let amount = AmountSource::native_balance(
+        amount.map(AmountSpec::Exact),
+        denom.clone(),
+        Some(executor_addr),
+    )?
+    .resolve(&deps.querier)?;
 
     Ok(vec![CosmosMsg::Staking(
         cosmwasm_std::StakingMsg::Delegate {
@@ -31,14 +101,12 @@ pub fn query_undelegate_msg(
     denom: String,
     amount: Option<Uint128>,
 ) -> StdResult<Vec<CosmosMsg>> {
-    let amount = amount.unwrap_or_else(|| {
-        deps.querier
-            .query_delegation(executor_addr.to_string(), validator.clone())
-            .unwrap()
-            .unwrap()
-            .amount
-            .amount
-    });
+    let amount = AmountSource::delegation(
+        amount.map(AmountSpec::Exact),
+        executor_addr,
+        validator.clone(),
+    )
+    .resolve(&deps.querier)?;
 
     Ok(vec![CosmosMsg::Staking(
         cosmwasm_std::StakingMsg::Undelegate {
@@ -47,3 +115,313 @@ pub fn query_undelegate_msg(
         },
     )])
 }
+
+/// Split `amount` proportionally to `weights`. The rounding dust goes to the
+/// largest weight, the first one on ties. With `capped` no share exceeds its
+/// weight and dust spills over to the next largest.
+fn split_by_weight(amount: Uint128, weights: &[Uint128], capped: bool) -> StdResult<Vec<Uint128>> {
+    let total = weights
+        .iter()
+        .try_fold(Uint128::zero(), |acc, weight| acc.checked_add(*weight))?;
+    if total.is_zero() {
+        return Err(StdError::generic_err("Total weight is zero"));
+    }
+
+    let mut shares: Vec<Uint128> = weights
+        .iter()
+        .map(|weight| amount.multiply_ratio(*weight, total))
+        .collect();
+    let distributed = shares
+        .iter()
+        .fold(Uint128::zero(), |acc, share| acc + share);
+    let mut dust = amount - distributed;
+
+    let mut order: Vec<usize> = (0..weights.len()).collect();
+    order.sort_by(|a, b| weights[*b].cmp(&weights[*a]));
+    for index in order {
+        if dust.is_zero() {
+            break;
+        }
+        let room = if capped {
+            weights[index].saturating_sub(shares[index])
+        } else {
+            dust
+        };
+        let extra = room.min(dust);
+        shares[index] += extra;
+        dust -= extra;
+    }
+
+    Ok(shares)
+}
+
+pub fn query_delegate_split_msg(
+    deps: Deps,
+    executor_addr: Addr,
+    denom: String,
+    amount: Option<Uint128>,
+    validators: Vec<(String, Decimal)>,
+) -> StdResult<Vec<CosmosMsg>> {
+    if validators.is_empty() {
+        return Err(StdError::GenericErr {
+            msg: "Validators is empty!!!".to_string(),
+        });
+    }
+
+    let amount = AmountSource::native_balance(
+        amount.map(AmountSpec::Exact),
+        denom.clone(),
+        Some(executor_addr.clone()),
+    )?
+    .resolve(&deps.querier)?;
+
+    let weights: Vec<Uint128> = validators
+        .iter()
+        .map(|(_, weight)| weight.atomics())
+        .collect();
+    let shares = split_by_weight(amount, &weights, false)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for ((validator, _), share) in validators.into_iter().zip(shares) {
+        if share.is_zero() {
+            continue;
+        }
+        messages.extend(query_delegate_msg(
+            deps,
+            executor_addr.clone(),
+            validator,
+            denom.clone(),
+            Some(share),
+        )?);
+    }
+    Ok(messages)
+}
+
+pub fn query_undelegate_proportional_msg(
+    deps: Deps,
+    executor_addr: Addr,
+    denom: String,
+    amount: Option<Uint128>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let delegations: Vec<(String, Uint128)> = deps
+        .querier
+        .query_all_delegations(executor_addr.as_str())?
+        .into_iter()
+        .filter(|delegation| {
+            delegation.amount.denom == denom && !delegation.amount.amount.is_zero()
+        })
+        .map(|delegation| (delegation.validator, delegation.amount.amount))
+        .collect();
+    if delegations.is_empty() {
+        return Err(OperationsError::NoDelegations {
+            delegator: executor_addr.to_string(),
+        }
+        .into());
+    }
+
+    let delegated: Vec<Uint128> = delegations.iter().map(|(_, amount)| *amount).collect();
+    let shares = match amount {
+        Some(amount) => {
+            let total = delegated
+                .iter()
+                .fold(Uint128::zero(), |acc, amount| acc + amount);
+            if amount > total {
+                return Err(StdError::generic_err(format!(
+                    "Undelegate amount {} exceeds total delegation {}",
+                    amount, total
+                )));
+            }
+            split_by_weight(amount, &delegated, true)?
+        }
+        None => delegated,
+    };
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for ((validator, _), share) in delegations.into_iter().zip(shares) {
+        if share.is_zero() {
+            continue;
+        }
+        messages.extend(query_undelegate_msg(
+            deps,
+            executor_addr.clone(),
+            validator,
+            denom.clone(),
+            Some(share),
+        )?);
+    }
+    Ok(messages)
+}
+
+pub fn query_redelegate_msg(
+    deps: Deps,
+    executor_addr: Addr,
+    src_validator: String,
+    dst_validator: String,
+    denom: String,
+    amount: Option<Uint128>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let amount = AmountSource::delegation(
+        amount.map(AmountSpec::Exact),
+        executor_addr,
+        src_validator.clone(),
+    )
+    .resolve(&deps.querier)?;
+
+    Ok(vec![CosmosMsg::Staking(
+        cosmwasm_std::StakingMsg::Redelegate {
+            src_validator,
+            dst_validator,
+            amount: Coin { denom, amount },
+        },
+    )])
+}
+
+/// `validator` if set, otherwise every validator `executor_addr` delegates to.
+fn query_reward_validators(
+    deps: Deps,
+    executor_addr: &Addr,
+    validator: Option<String>,
+) -> StdResult<Vec<String>> {
+    if let Some(validator) = validator {
+        return Ok(vec![validator]);
+    }
+
+    let validators: Vec<String> = deps
+        .querier
+        .query_all_delegations(executor_addr.as_str())?
+        .into_iter()
+        .map(|delegation| delegation.validator)
+        .collect();
+    if validators.is_empty() {
+        return Err(OperationsError::NoDelegations {
+            delegator: executor_addr.to_string(),
+        }
+        .into());
+    }
+    Ok(validators)
+}
+
+pub fn query_claim_rewards_msg(
+    deps: Deps,
+    executor_addr: Addr,
+    validator: Option<String>,
+) -> StdResult<Vec<CosmosMsg>> {
+    Ok(query_reward_validators(deps, &executor_addr, validator)?
+        .into_iter()
+        .map(|validator| {
+            CosmosMsg::Distribution(DistributionMsg::WithdrawDelegatorReward { validator })
+        })
+        .collect())
+}
+
+pub fn query_set_withdraw_address_msg(address: String) -> StdResult<Vec<CosmosMsg>> {
+    Ok(vec![CosmosMsg::Distribution(
+        DistributionMsg::SetWithdrawAddress { address },
+    )])
+}
+
+pub fn query_claim_and_restake_msg(
+    deps: Deps,
+    executor_addr: Addr,
+    validator: Option<String>,
+    denom: String,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut messages: Vec<CosmosMsg> = vec![];
+
+    for validator in query_reward_validators(deps, &executor_addr, validator)? {
+        let delegation = deps
+            .querier
+            .query_delegation(executor_addr.as_str(), validator.as_str())?
+            .ok_or_else(|| OperationsError::NoDelegation {
+                validator: validator.clone(),
+            })?;
+        let reward = delegation
+            .accumulated_rewards
+            .iter()
+            .find(|coin| coin.denom == denom)
+            .map(|coin| coin.amount)
+            .unwrap_or_default();
+
+        messages.push(CosmosMsg::Distribution(
+            DistributionMsg::WithdrawDelegatorReward {
+                validator: validator.clone(),
+            },
+        ));
+        // rewards are paid out before the delegation in the same tx
+        if !reward.is_zero() {
+            messages.push(CosmosMsg::Staking(cosmwasm_std::StakingMsg::Delegate {
+                validator,
+                amount: Coin {
+                    denom: denom.clone(),
+                    amount: reward,
+                },
+            }));
+        }
+    }
+
+    Ok(messages)
+}
+
+pub fn query_vote_msg(proposal_id: u64, vote: VoteOption) -> StdResult<Vec<CosmosMsg>> {
+    Ok(vec![CosmosMsg::Gov(GovMsg::Vote { proposal_id, vote })])
+}
+
+pub fn query_weighted_vote_msg(
+    proposal_id: u64,
+    options: Vec<WeightedVoteOption>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let total = options
+        .iter()
+        .fold(Decimal::zero(), |acc, option| acc + option.weight);
+    if total != Decimal::one() {
+        return Err(StdError::generic_err(format!(
+            "Vote weights add up to {}, expected 1",
+            total
+        )));
+    }
+
+    Ok(vec![CosmosMsg::Gov(GovMsg::VoteWeighted {
+        proposal_id,
+        options,
+    })])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uints(values: &[u128]) -> Vec<Uint128> {
+        values.iter().map(|value| Uint128::new(*value)).collect()
+    }
+
+    #[test]
+    fn split_gives_dust_to_largest_weight() {
+        let shares = split_by_weight(Uint128::new(100), &uints(&[1, 1, 1]), false).unwrap();
+        assert_eq!(shares, uints(&[34, 33, 33]));
+
+        let shares = split_by_weight(Uint128::new(10), &uints(&[1, 3, 3]), false).unwrap();
+        assert_eq!(shares, uints(&[1, 5, 4]));
+    }
+
+    #[test]
+    fn capped_split_spills_dust_over() {
+        // pro-rata shares are 4, 1 and 4: the first largest weight fills
+        // up to its cap, the rest of the dust goes to the next largest
+        let shares = split_by_weight(Uint128::new(11), &uints(&[5, 2, 5]), true).unwrap();
+        assert_eq!(shares, uints(&[5, 1, 5]));
+        assert_eq!(
+            shares
+                .iter()
+                .fold(Uint128::zero(), |acc, share| acc + share),
+            Uint128::new(11)
+        );
+    }
+
+    #[test]
+    fn split_rejects_zero_and_overflowing_weights() {
+        assert!(split_by_weight(Uint128::new(10), &uints(&[0, 0]), false).is_err());
+        assert!(
+            split_by_weight(Uint128::new(10), &[Uint128::MAX, Uint128::new(1)], false).is_err()
+        );
+    }
+}
@@ -1,39 +1,36 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    to_binary, Addr, Api, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+};
+use operations_core::{
+    instantiate_entry_point, migrate_entry_point,
+    operation::{build_batch_messages, build_messages},
+    ownership::update_config,
+    Operation,
 };
 
 use crate::{
-    chain::{query_delegate_msg, query_undelegate_msg},
     error::ContractError,
-    msg::{
-        ChainOperations, ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
-        TokenOperations, WrappedOperations,
-    },
+    msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UtilityOperation},
     state::{Config, CONFIG},
-    tokens::{
-        query_token_send_from_msg, query_token_send_msg, query_token_transfer_from_msg,
-        query_token_transfer_msg,
-    },
-    wrapped::{query_wrapped_convert_to_cw20, query_wrapped_convert_to_denom},
+};
+use oraidex::{
+    oracle::OracleContract,
+    tax::{query_messages_tax, query_tax, MessagesWithTaxResponse},
 };
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    msg: InstantiateMsg,
-) -> Result<Response, ContractError> {
-    CONFIG.save(
-        deps.storage,
-        &Config {
-            owner: deps.api.addr_canonicalize(msg.owner.as_str())?,
-            wrapped_contract: deps.api.addr_canonicalize(msg.wrapped_contract.as_str())?,
-        },
-    )?;
-    Ok(Response::default())
+instantiate_entry_point!(InstantiateMsg, CONFIG, config_from_msg);
+
+fn config_from_msg(api: &dyn Api, msg: InstantiateMsg) -> StdResult<Config> {
+    Ok(Config {
+        owner: api.addr_canonicalize(msg.owner.as_str())?,
+        wrapped_contract: api.addr_canonicalize(msg.wrapped_contract.as_str())?,
+        oraiswap_oracle: msg
+            .oraiswap_oracle
+            .map(|oracle| api.addr_canonicalize(oracle.as_str()))
+            .transpose()?,
+    })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -47,7 +44,8 @@ pub fn execute(
         ExecuteMsg::UpdateConfig {
             owner,
             wrapped_contract,
-        } => execute_update_config(deps, info, owner, wrapped_contract),
+            oraiswap_oracle,
+        } => execute_update_config(deps, info, owner, wrapped_contract, oraiswap_oracle),
     }
 }
 
@@ -56,131 +54,74 @@ pub fn execute_update_config(
     info: MessageInfo,
     owner: Option<Addr>,
     wrapped_contract: Option<Addr>,
+    oraiswap_oracle: Option<Addr>,
 ) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
-    if deps.api.addr_humanize(&config.owner)? != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+    Ok(update_config(
+        deps,
+        &info,
+        &CONFIG,
+        owner,
+        |api, config| {
+            if let Some(wrapped_contract) = wrapped_contract {
+                config.wrapped_contract = api.addr_canonicalize(wrapped_contract.as_str())?;
+            }
 
-    if let Some(owner) = owner {
-        config.owner = deps.api.addr_canonicalize(owner.as_str())?;
-    }
-
-    if let Some(wrapped_contract) = wrapped_contract {
-        config.wrapped_contract = deps.api.addr_canonicalize(wrapped_contract.as_str())?;
-    }
-
-    CONFIG.save(deps.storage, &config)?;
-
-    Ok(Response::new().add_attribute("action", "update_config"))
+            if let Some(oraiswap_oracle) = oraiswap_oracle {
+                config.oraiswap_oracle = Some(api.addr_canonicalize(oraiswap_oracle.as_str())?);
+            }
+            Ok(())
+        },
+    )?)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
-        QueryMsg::Messages { msg } => match from_binary(&msg) {
-            Ok(TokenOperations::TokenTransfer {
-                token,
-                sender,
-                recipient,
-                amount,
-            }) => to_binary(&query_token_transfer_msg(
-                deps, token, sender, recipient, amount,
-            )?),
-            Ok(TokenOperations::TokenTransferFrom {
-                token,
-                owner,
-                recipient,
-                amount,
-            }) => to_binary(&query_token_transfer_from_msg(
-                deps, token, owner, recipient, amount,
-            )?),
-            Ok(TokenOperations::TokenSend {
-                token,
-                sender,
-                contract,
-                amount,
-                msg,
-            }) => to_binary(&query_token_send_msg(
-                deps, token, sender, contract, amount, msg,
-            )?),
-            Ok(TokenOperations::TokenSendFrom {
-                token,
-                owner,
-                contract,
-                amount,
-                msg,
-            }) => to_binary(&query_token_send_from_msg(
-                deps, token, owner, contract, amount, msg,
-            )?),
-            _ => match from_binary(&msg) {
-                Ok(WrappedOperations::ConvertToCw20 {
-                    from_denom,
-                    executor_addr,
-                    amount,
-                }) => to_binary(&query_wrapped_convert_to_cw20(
-                    deps,
-                    from_denom,
-                    executor_addr,
-                    amount,
-                )?),
-                Ok(WrappedOperations::ConvertToDenom {
-                    from_token,
-                    target_denom,
-                    executor_addr,
-                    sender,
-                    amount,
-                }) => to_binary(&query_wrapped_convert_to_denom(
-                    deps,
-                    from_token,
-                    target_denom,
-                    executor_addr,
-                    sender,
-                    amount,
-                )?),
-                _ => match from_binary(&msg) {
-                    Ok(ChainOperations::ChainDelegate {
-                        executor_addr,
-                        validator,
-                        denom,
-                        amount,
-                    }) => to_binary(&query_delegate_msg(
-                        deps,
-                        executor_addr,
-                        validator,
-                        denom,
-                        amount,
-                    )?),
-                    Ok(ChainOperations::ChainUndelegate {
-                        executor_addr,
-                        validator,
-                        denom,
-                        amount,
-                    }) => to_binary(&query_undelegate_msg(
-                        deps,
-                        executor_addr,
-                        validator,
-                        denom,
-                        amount,
-                    )?),
-                    _ => Err(cosmwasm_std::StdError::NotFound {
-                        kind: "Operations not found".to_string(),
-                    }),
-                },
-            },
-        },
+        QueryMsg::Messages { msg } => {
+            to_binary(&build_messages::<UtilityOperation>(deps, env, &msg)?)
+        }
+        QueryMsg::BatchMessages { msgs } => to_binary(&build_batch_messages(
+            deps,
+            env,
+            &msgs,
+            build_messages::<UtilityOperation>,
+        )?),
+        QueryMsg::Tax { asset } => {
+            to_binary(&query_tax(&deps.querier, &load_oracle(deps)?, &asset)?)
+        }
+        QueryMsg::MessagesWithTax { msg } => {
+            let messages = build_messages::<UtilityOperation>(deps, env, &msg)?;
+            let tax = query_messages_tax(&deps.querier, &load_oracle(deps)?, &messages)?;
+            to_binary(&MessagesWithTaxResponse { messages, tax })
+        }
+    }
+}
+
+impl Operation for UtilityOperation {
+    fn build(self, deps: Deps, env: Env) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            UtilityOperation::Token(operation) => operation.build(deps, env),
+            UtilityOperation::Wrapped(operation) => operation.build(deps, env),
+            UtilityOperation::Chain(operation) => operation.build(deps, env),
+        }
     }
 }
 
+pub fn load_oracle(deps: Deps) -> StdResult<OracleContract> {
+    let config = CONFIG.load(deps.storage)?;
+    OracleContract::from_config(deps.api, config.oraiswap_oracle.as_ref())
+}
+
 fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
     Ok(ConfigResponse {
         owner: deps.api.addr_humanize(&config.owner)?,
+        oraiswap_oracle: config
+            .oraiswap_oracle
+            .map(|oracle| deps.api.addr_humanize(&oracle))
+            .transpose()?,
     })
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
-}
+migrate_entry_point!(MigrateMsg);
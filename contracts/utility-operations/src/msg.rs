@@ -1,10 +1,16 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, CosmosMsg, Uint128};
+use cosmwasm_std::{Addr, Binary, CosmosMsg, Decimal, Uint128, VoteOption, WeightedVoteOption};
+use operations_core::{operation::BatchMessagesResponse, AmountSpec};
+use oraidex::{
+    asset::{Asset, AssetInfo},
+    tax::{MessagesWithTaxResponse, TaxResponse},
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub owner: Addr,
     pub wrapped_contract: Addr,
+    pub oraiswap_oracle: Option<Addr>,
 }
 
 #[cw_serde]
@@ -12,6 +18,7 @@ pub enum ExecuteMsg {
     UpdateConfig {
         owner: Option<Addr>,
         wrapped_contract: Option<Addr>,
+        oraiswap_oracle: Option<Addr>,
     },
 }
 
@@ -20,46 +27,85 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(Vec<CosmosMsg>)]
     Messages { msg: Binary },
+    #[returns(BatchMessagesResponse)]
+    BatchMessages { msgs: Vec<Binary> },
     #[returns(ConfigResponse)]
     Config {},
+    /// Tax charged on a native transfer of `asset`
+    #[returns(TaxResponse)]
+    Tax { asset: Asset },
+    /// Messages of the operation `msg` with the tax the chain charges on them
+    #[returns(MessagesWithTaxResponse)]
+    MessagesWithTax { msg: Binary },
 }
 
 #[cw_serde]
 pub struct ConfigResponse {
     pub owner: Addr,
+    pub oraiswap_oracle: Option<Addr>,
 }
 
 #[cw_serde]
 pub struct MigrateMsg {}
 
+/// Operation json accepted by `QueryMsg::Messages`, namespaced by module,
+/// e.g. `{"token": {"token_transfer": {...}}}`.
+#[cw_serde]
+pub enum UtilityOperation {
+    Token(TokenOperations),
+    Wrapped(WrappedOperations),
+    Chain(ChainOperations),
+}
+
 #[cw_serde]
 pub enum TokenOperations {
     TokenTransfer {
         token: Addr,
         sender: Option<Addr>,
         recipient: Addr,
-        amount: Option<Uint128>,
+        amount: Option<AmountSpec>,
     },
     TokenTransferFrom {
         token: Addr,
         owner: Addr,
         recipient: Addr,
-        amount: Option<Uint128>,
+        amount: Option<AmountSpec>,
     },
     TokenSend {
         token: Addr,
         sender: Option<Addr>,
         contract: Addr,
-        amount: Option<Uint128>,
+        amount: Option<AmountSpec>,
         msg: Binary,
     },
     TokenSendFrom {
         token: Addr,
         owner: Addr,
         contract: Addr,
-        amount: Option<Uint128>,
+        amount: Option<AmountSpec>,
         msg: Binary,
     },
+    /// Transfer a native or cw20 asset from the executor
+    AssetTransfer {
+        asset_info: AssetInfo,
+        sender: Option<Addr>,
+        recipient: Addr,
+        amount: Option<AmountSpec>,
+        /// Send a native amount net of the chain tax
+        #[serde(default)]
+        deduct_tax: bool,
+    },
+    /// Send a native or cw20 asset from the executor to a contract along with `msg`
+    AssetSend {
+        asset_info: AssetInfo,
+        sender: Option<Addr>,
+        contract: Addr,
+        amount: Option<AmountSpec>,
+        msg: Binary,
+        /// Send a native amount net of the chain tax
+        #[serde(default)]
+        deduct_tax: bool,
+    },
 }
 
 #[cw_serde]
@@ -92,4 +138,48 @@ pub enum ChainOperations {
         denom: String,
         amount: Option<Uint128>,
     },
+    /// Split the amount across `validators` by weight, rounding dust goes to the largest one
+    DelegateSplit {
+        executor_addr: Addr,
+        denom: String,
+        amount: Option<Uint128>,
+        validators: Vec<(String, Decimal)>,
+    },
+    /// Undelegate pro-rata from every delegation of `executor_addr`, all of them if `amount` is unset
+    UndelegateProportional {
+        executor_addr: Addr,
+        denom: String,
+        amount: Option<Uint128>,
+    },
+    ChainRedelegate {
+        executor_addr: Addr,
+        src_validator: String,
+        dst_validator: String,
+        denom: String,
+        amount: Option<Uint128>,
+    },
+    /// Withdraw rewards from `validator`, or from every validator `executor_addr` delegates to
+    ChainClaimRewards {
+        executor_addr: Addr,
+        validator: Option<String>,
+    },
+    ChainSetWithdrawAddress {
+        address: String,
+    },
+    /// Withdraw rewards like `ChainClaimRewards` and delegate the `denom` rewards
+    /// back to the validator that paid them
+    ChainClaimAndRestake {
+        executor_addr: Addr,
+        validator: Option<String>,
+        denom: String,
+    },
+    Vote {
+        proposal_id: u64,
+        option: VoteOption,
+    },
+    /// Split the vote across options, weights must add up to 1
+    WeightedVote {
+        proposal_id: u64,
+        options: Vec<WeightedVoteOption>,
+    },
 }
@@ -1,6 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::CanonicalAddr;
 use cw_storage_plus::Item;
+use operations_core::impl_owned_config;
 
 // put the length bytes at the first for compatibility with legacy singleton store
 pub const CONFIG: Item<Config> = Item::new("\u{0}\u{6}config");
@@ -9,4 +10,7 @@ pub const CONFIG: Item<Config> = Item::new("\u{0}\u{6}config");
 pub struct Config {
     pub owner: CanonicalAddr,
     pub wrapped_contract: CanonicalAddr,
+    pub oraiswap_oracle: Option<CanonicalAddr>,
 }
+
+impl_owned_config!(Config);
@@ -1,32 +1,69 @@
-use cosmwasm_std::{to_binary, Addr, Binary, CosmosMsg, Deps, StdError, StdResult, Uint128};
-use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, Env, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use operations_core::{AmountSource, AmountSpec, Operation};
+use oraidex::{asset::AssetInfo, tax::amount_after_tax};
+
+use crate::{contract::load_oracle, msg::TokenOperations};
+
+impl Operation for TokenOperations {
+    fn build(self, deps: Deps, _env: Env) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            TokenOperations::TokenTransfer {
+                token,
+                sender,
+                recipient,
+                amount,
+            } => query_token_transfer_msg(deps, token, sender, recipient, amount),
+            TokenOperations::TokenTransferFrom {
+                token,
+                owner,
+                recipient,
+                amount,
+            } => query_token_transfer_from_msg(deps, token, owner, recipient, amount),
+            TokenOperations::TokenSend {
+                token,
+                sender,
+                contract,
+                amount,
+                msg,
+            } => query_token_send_msg(deps, token, sender, contract, amount, msg),
+            TokenOperations::TokenSendFrom {
+                token,
+                owner,
+                contract,
+                amount,
+                msg,
+            } => query_token_send_from_msg(deps, token, owner, contract, amount, msg),
+            TokenOperations::AssetTransfer {
+                asset_info,
+                sender,
+                recipient,
+                amount,
+                deduct_tax,
+            } => query_asset_transfer_msg(deps, asset_info, sender, recipient, amount, deduct_tax),
+            TokenOperations::AssetSend {
+                asset_info,
+                sender,
+                contract,
+                amount,
+                msg,
+                deduct_tax,
+            } => query_asset_send_msg(deps, asset_info, sender, contract, amount, msg, deduct_tax),
+        }
+    }
+}
 
 pub fn query_token_transfer_msg(
     deps: Deps,
     token: Addr,
     sender: Option<Addr>,
     recipient: Addr,
-    amount: Option<Uint128>,
+    amount: Option<AmountSpec>,
 ) -> StdResult<Vec<CosmosMsg>> {
-    if sender.is_none() && amount.is_none() {
-        return Err(StdError::GenericErr {
-            msg: "Sender and amount is none!!!".to_string(),
-        });
-    }
-
-    let amount = amount.unwrap_or_else(|| {
-        let balance: BalanceResponse = deps
-            .querier
-            .query_wasm_smart(
-                token.to_string(),
-                &Cw20QueryMsg::Balance {
-                    address: sender.unwrap().to_string(),
-                },
-            )
-            .unwrap();
-
-        balance.balance
-    });
+    let amount =
+        AmountSource::token_balance(amount, token.clone(), sender)?.resolve(&deps.querier)?;
 
     Ok(vec![CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
         contract_addr: token.to_string(),
@@ -43,21 +80,11 @@ pub fn query_token_transfer_from_msg(
     token: Addr,
     owner: Addr,
     recipient: Addr,
-    amount: Option<Uint128>,
+    amount: Option<AmountSpec>,
 ) -> StdResult<Vec<CosmosMsg>> {
-    let amount = amount.unwrap_or_else(|| {
-        let balance: BalanceResponse = deps
-            .querier
-            .query_wasm_smart(
-                token.to_string(),
-                &Cw20QueryMsg::Balance {
-                    address: owner.to_string(),
-                },
-            )
-            .unwrap();
+    let amount = AmountSource::token_balance(amount, token.clone(), Some(owner.clone()))?
+        .resolve(&deps.querier)?;
 
-        balance.balance
-    });
     Ok(vec![CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
         contract_addr: token.to_string(),
         msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
@@ -74,28 +101,11 @@ pub fn query_token_send_msg(
     token: Addr,
     sender: Option<Addr>,
     contract: Addr,
-    amount: Option<Uint128>,
+    amount: Option<AmountSpec>,
     msg: Binary,
 ) -> StdResult<Vec<CosmosMsg>> {
-    if sender.is_none() && amount.is_none() {
-        return Err(StdError::GenericErr {
-            msg: "Sender and amount is none!!!".to_string(),
-        });
-    }
-
-    let amount = amount.unwrap_or_else(|| {
-        let balance: BalanceResponse = deps
-            .querier
-            .query_wasm_smart(
-                token.to_string(),
-                &Cw20QueryMsg::Balance {
-                    address: sender.unwrap().to_string(),
-                },
-            )
-            .unwrap();
-
-        balance.balance
-    });
+    let amount =
+        AmountSource::token_balance(amount, token.clone(), sender)?.resolve(&deps.querier)?;
 
     Ok(vec![CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
         contract_addr: token.to_string(),
@@ -113,22 +123,12 @@ pub fn query_token_send_from_msg(
     token: Addr,
     owner: Addr,
     contract: Addr,
-    amount: Option<Uint128>,
+    amount: Option<AmountSpec>,
     msg: Binary,
 ) -> StdResult<Vec<CosmosMsg>> {
-    let amount = amount.unwrap_or_else(|| {
-        let balance: BalanceResponse = deps
-            .querier
-            .query_wasm_smart(
-                token.to_string(),
-                &Cw20QueryMsg::Balance {
-                    address: owner.to_string(),
-                },
-            )
-            .unwrap();
+    let amount = AmountSource::token_balance(amount, token.clone(), Some(owner.clone()))?
+        .resolve(&deps.querier)?;
 
-        balance.balance
-    });
     Ok(vec![CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
         contract_addr: token.to_string(),
         msg: to_binary(&Cw20ExecuteMsg::SendFrom {
@@ -140,3 +140,85 @@ pub fn query_token_send_from_msg(
         funds: vec![],
     })])
 }
+
+fn query_asset_amount(
+    deps: Deps,
+    asset_info: &AssetInfo,
+    holder: Option<Addr>,
+    amount: Option<AmountSpec>,
+    deduct_tax: bool,
+) -> StdResult<Uint128> {
+    let source = match asset_info {
+        AssetInfo::NativeToken { denom } => {
+            AmountSource::native_balance(amount, denom.clone(), holder)?
+        }
+        AssetInfo::Token { contract_addr } => {
+            AmountSource::token_balance(amount, contract_addr.clone(), holder)?
+        }
+    };
+    let amount = source.resolve(&deps.querier)?;
+    amount_after_tax(
+        &deps.querier,
+        || load_oracle(deps),
+        asset_info,
+        amount,
+        deduct_tax,
+    )
+}
+
+pub fn query_asset_transfer_msg(
+    deps: Deps,
+    asset_info: AssetInfo,
+    sender: Option<Addr>,
+    recipient: Addr,
+    amount: Option<AmountSpec>,
+    deduct_tax: bool,
+) -> StdResult<Vec<CosmosMsg>> {
+    let amount = query_asset_amount(deps, &asset_info, sender, amount, deduct_tax)?;
+
+    let msg = match asset_info {
+        AssetInfo::NativeToken { denom } => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin { denom, amount }],
+        }),
+        AssetInfo::Token { contract_addr } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }),
+    };
+    Ok(vec![msg])
+}
+
+pub fn query_asset_send_msg(
+    deps: Deps,
+    asset_info: AssetInfo,
+    sender: Option<Addr>,
+    contract: Addr,
+    amount: Option<AmountSpec>,
+    msg: Binary,
+    deduct_tax: bool,
+) -> StdResult<Vec<CosmosMsg>> {
+    let amount = query_asset_amount(deps, &asset_info, sender, amount, deduct_tax)?;
+
+    let msg = match asset_info {
+        AssetInfo::NativeToken { denom } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg,
+            funds: vec![Coin { denom, amount }],
+        }),
+        AssetInfo::Token { contract_addr } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: contract.to_string(),
+                amount,
+                msg,
+            })?,
+            funds: vec![],
+        }),
+    };
+    Ok(vec![msg])
+}
@@ -1,10 +1,37 @@
 use std::vec;
 
-use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, Deps, StdResult, Uint128};
+use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, Deps, Env, StdResult, Uint128};
 use cw20::Cw20ExecuteMsg;
-use utility::{querier::query_token_balance, wrapped::WrappedExecuteMsg};
+use operations_core::{AmountSource, AmountSpec, Operation};
+use utility::wrapped::WrappedExecuteMsg;
 
-use crate::state::CONFIG;
+use crate::{msg::WrappedOperations, state::CONFIG};
+
+impl Operation for WrappedOperations {
+    fn build(self, deps: Deps, _env: Env) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            WrappedOperations::ConvertToDenom {
+                from_token,
+                target_denom,
+                executor_addr,
+                sender,
+                amount,
+            } => query_wrapped_convert_to_denom(
+                deps,
+                from_token,
+                target_denom,
+                executor_addr,
+                sender,
+                amount,
+            ),
+            WrappedOperations::ConvertToCw20 {
+                from_denom,
+                executor_addr,
+                amount,
+            } => query_wrapped_convert_to_cw20(deps, from_denom, executor_addr, amount),
+        }
+    }
+}
 
 pub fn query_wrapped_convert_to_denom(
     deps: Deps,
@@ -18,9 +45,12 @@ pub fn query_wrapped_convert_to_denom(
     let wrapped_contract = deps.api.addr_humanize(&config.wrapped_contract)?;
 
     let mut messages: Vec<CosmosMsg> = vec![];
-    let amount = amount.unwrap_or_else(|| {
-        query_token_balance(&deps.querier, from_token.clone(), sender.clone()).unwrap()
-    });
+    let amount = AmountSource::token_balance(
+        amount.map(AmountSpec::Exact),
+        from_token.clone(),
+        Some(sender.clone()),
+    )?
+    .resolve(&deps.querier)?;
 
     if sender != executor_addr {
         messages.push(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
@@ -56,12 +86,12 @@ pub fn query_wrapped_convert_to_cw20(
     let config = CONFIG.load(deps.storage)?;
     let wrapped_contract = deps.api.addr_humanize(&config.wrapped_contract)?;
 
-    let amount = amount.unwrap_or_else(|| {
-        deps.querier
-            .query_balance(executor_addr.to_string(), from_denom.clone())
-            .unwrap()
-            .amount
-    });
+    let amount = AmountSource::native_balance(
+        amount.map(AmountSpec::Exact),
+        from_denom.clone(),
+        Some(executor_addr),
+    )?
+    .resolve(&deps.querier)?;
 
     Ok(vec![CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
         contract_addr: wrapped_contract.to_string(),
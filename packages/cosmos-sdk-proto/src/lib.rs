@@ -42,6 +42,13 @@ pub mod cosmos {
         }
     }
 
+    /// Messages and services handling governance.
+    pub mod gov {
+        pub mod v1beta1 {
+            include!("prost/cosmos-sdk/cosmos.gov.v1beta1.rs");
+        }
+    }
+
     pub mod staking {
         pub mod v1beta1 {
             include!("prost/cosmos-sdk/cosmos.staking.v1beta1.rs");
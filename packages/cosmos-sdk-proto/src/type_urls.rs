@@ -38,3 +38,11 @@ impl TypeUrl for cosmos::staking::v1beta1::MsgBeginRedelegate {
 impl TypeUrl for cosmos::distribution::v1beta1::MsgWithdrawDelegatorReward {
     const TYPE_URL: &'static str = "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward";
 }
+
+impl TypeUrl for cosmos::gov::v1beta1::MsgVote {
+    const TYPE_URL: &'static str = "/cosmos.gov.v1beta1.MsgVote";
+}
+
+impl TypeUrl for cosmos::gov::v1beta1::MsgVoteWeighted {
+    const TYPE_URL: &'static str = "/cosmos.gov.v1beta1.MsgVoteWeighted";
+}
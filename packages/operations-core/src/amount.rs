@@ -0,0 +1,313 @@
+use cosmwasm_schema::schemars::JsonSchema;
+use cosmwasm_std::{Addr, Decimal, QuerierWrapper, Uint128};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use serde::{Deserialize, Serialize};
+
+use crate::OperationsError;
+
+/// How much of the available balance an operation uses.
+///
+/// A bare amount such as `"100"` is read as `Exact`, as recipes sent it
+/// before amounts could be derived from balances.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+#[serde(
+    deny_unknown_fields,
+    rename_all = "snake_case",
+    from = "AmountSpecRepr"
+)]
+#[schemars(crate = "cosmwasm_schema::schemars")]
+pub enum AmountSpec {
+    Exact(Uint128),
+    #[default]
+    All,
+    /// Share of the balance, between 0 and 1
+    Ratio(Decimal),
+    /// Balance minus a reserve kept by the holder, e.g. for fees
+    AllExcept(Uint128),
+    /// Balance, but at most the given cap
+    Capped(Uint128),
+}
+
+#[derive(Deserialize)]
+#[serde(remote = "AmountSpec", deny_unknown_fields, rename_all = "snake_case")]
+enum TaggedAmountSpec {
+    Exact(Uint128),
+    All,
+    Ratio(Decimal),
+    AllExcept(Uint128),
+    Capped(Uint128),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AmountSpecRepr {
+    Bare(Uint128),
+    Tagged(#[serde(with = "TaggedAmountSpec")] AmountSpec),
+}
+
+impl From<AmountSpecRepr> for AmountSpec {
+    fn from(repr: AmountSpecRepr) -> Self {
+        match repr {
+            AmountSpecRepr::Bare(amount) => AmountSpec::Exact(amount),
+            AmountSpecRepr::Tagged(spec) => spec,
+        }
+    }
+}
+
+impl AmountSpec {
+    /// Apply the spec to the full `balance` of the holder. A spec other than
+    /// `Exact` resolving to zero is rejected, cw20 contracts refuse such
+    /// transfers.
+    pub fn apply(&self, balance: Uint128) -> Result<Uint128, OperationsError> {
+        let amount = match self {
+            AmountSpec::Exact(amount) => return Ok(*amount),
+            AmountSpec::All => balance,
+            AmountSpec::Ratio(ratio) => {
+                if *ratio > Decimal::one() {
+                    return Err(OperationsError::InvalidRatio { ratio: *ratio });
+                }
+                balance * *ratio
+            }
+            AmountSpec::AllExcept(reserve) => {
+                if balance <= *reserve {
+                    return Err(OperationsError::ReserveExceedsBalance {
+                        reserve: *reserve,
+                        balance,
+                    });
+                }
+                balance - *reserve
+            }
+            AmountSpec::Capped(cap) => balance.min(*cap),
+        };
+        if amount.is_zero() {
+            return Err(OperationsError::ZeroAmount {});
+        }
+        Ok(amount)
+    }
+}
+
+/// Where the amount of an operation comes from: either given exactly, or
+/// derived by `spec` from a balance queried at build time.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AmountSource {
+    Explicit(Uint128),
+    /// `denom` bank balance of `holder`
+    NativeBalance {
+        denom: String,
+        holder: Addr,
+        spec: AmountSpec,
+    },
+    /// cw20 `token` balance of `holder`
+    TokenBalance {
+        token: Addr,
+        holder: Addr,
+        spec: AmountSpec,
+    },
+    /// Amount `delegator` has delegated to `validator`
+    #[cfg(feature = "staking")]
+    Delegation {
+        delegator: Addr,
+        validator: String,
+        spec: AmountSpec,
+    },
+}
+
+impl AmountSource {
+    /// `amount` applied to the `denom` balance of `holder`, the full balance if unset.
+    pub fn native_balance(
+        amount: Option<AmountSpec>,
+        denom: impl Into<String>,
+        holder: Option<Addr>,
+    ) -> Result<Self, OperationsError> {
+        Ok(match amount.unwrap_or_default() {
+            AmountSpec::Exact(amount) => AmountSource::Explicit(amount),
+            spec => AmountSource::NativeBalance {
+                denom: denom.into(),
+                holder: require_holder(holder)?,
+                spec,
+            },
+        })
+    }
+
+    /// `amount` applied to the cw20 `token` balance of `holder`, the full balance if unset.
+    pub fn token_balance(
+        amount: Option<AmountSpec>,
+        token: Addr,
+        holder: Option<Addr>,
+    ) -> Result<Self, OperationsError> {
+        Ok(match amount.unwrap_or_default() {
+            AmountSpec::Exact(amount) => AmountSource::Explicit(amount),
+            spec => AmountSource::TokenBalance {
+                token,
+                holder: require_holder(holder)?,
+                spec,
+            },
+        })
+    }
+
+    /// `amount` applied to what `delegator` has delegated to `validator`, all of it if unset.
+    #[cfg(feature = "staking")]
+    pub fn delegation(
+        amount: Option<AmountSpec>,
+        delegator: Addr,
+        validator: impl Into<String>,
+    ) -> Self {
+        match amount.unwrap_or_default() {
+            AmountSpec::Exact(amount) => AmountSource::Explicit(amount),
+            spec => AmountSource::Delegation {
+                delegator,
+                validator: validator.into(),
+                spec,
+            },
+        }
+    }
+
+    /// Query the amount. Balances and delegations of zero are rejected, an
+    /// explicit amount is returned as is.
+    pub fn resolve(self, querier: &QuerierWrapper) -> Result<Uint128, OperationsError> {
+        match self {
+            AmountSource::Explicit(amount) => Ok(amount),
+            AmountSource::NativeBalance {
+                denom,
+                holder,
+                spec,
+            } => {
+                let balance = querier.query_balance(holder.as_str(), denom.as_str())?;
+                spec.apply(require_balance(balance.amount, denom, &holder)?)
+            }
+            AmountSource::TokenBalance {
+                token,
+                holder,
+                spec,
+            } => {
+                let balance: BalanceResponse = querier.query_wasm_smart(
+                    token.as_str(),
+                    &Cw20QueryMsg::Balance {
+                        address: holder.to_string(),
+                    },
+                )?;
+                spec.apply(require_balance(
+                    balance.balance,
+                    token.to_string(),
+                    &holder,
+                )?)
+            }
+            #[cfg(feature = "staking")]
+            AmountSource::Delegation {
+                delegator,
+                validator,
+                spec,
+            } => match querier.query_delegation(delegator.as_str(), validator.as_str())? {
+                Some(delegation) if !delegation.amount.amount.is_zero() => {
+                    spec.apply(delegation.amount.amount)
+                }
+                _ => Err(OperationsError::NoDelegation { validator }),
+            },
+        }
+    }
+}
+
+/// The address whose balance is used when no exact amount is given.
+pub fn require_holder(holder: Option<Addr>) -> Result<Addr, OperationsError> {
+    holder.ok_or(OperationsError::AmountOrSenderRequired {})
+}
+
+/// Reject a queried balance of zero, naming the `token` and its `holder`.
+pub fn require_balance(
+    balance: Uint128,
+    token: impl Into<String>,
+    holder: &Addr,
+) -> Result<Uint128, OperationsError> {
+    if balance.is_zero() {
+        return Err(OperationsError::ZeroBalance {
+            token: token.into(),
+            holder: holder.to_string(),
+        });
+    }
+    Ok(balance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{from_slice, to_vec};
+
+    #[test]
+    fn apply_specs() {
+        let balance = Uint128::new(1000);
+        assert_eq!(
+            AmountSpec::Exact(Uint128::new(5)).apply(balance),
+            Ok(Uint128::new(5))
+        );
+        assert_eq!(AmountSpec::All.apply(balance), Ok(balance));
+        assert_eq!(
+            AmountSpec::Ratio(Decimal::percent(25)).apply(balance),
+            Ok(Uint128::new(250))
+        );
+        assert_eq!(
+            AmountSpec::AllExcept(Uint128::new(100)).apply(balance),
+            Ok(Uint128::new(900))
+        );
+        assert_eq!(
+            AmountSpec::Capped(Uint128::new(100)).apply(balance),
+            Ok(Uint128::new(100))
+        );
+        assert_eq!(
+            AmountSpec::Capped(Uint128::new(5000)).apply(balance),
+            Ok(balance)
+        );
+    }
+
+    #[test]
+    fn apply_rejects_invalid_specs() {
+        let balance = Uint128::new(1000);
+        assert_eq!(
+            AmountSpec::Ratio(Decimal::percent(101)).apply(balance),
+            Err(OperationsError::InvalidRatio {
+                ratio: Decimal::percent(101)
+            })
+        );
+        assert_eq!(
+            AmountSpec::AllExcept(balance).apply(balance),
+            Err(OperationsError::ReserveExceedsBalance {
+                reserve: balance,
+                balance
+            })
+        );
+    }
+
+    #[test]
+    fn apply_rejects_zero_results() {
+        assert_eq!(
+            AmountSpec::Ratio(Decimal::percent(10)).apply(Uint128::new(5)),
+            Err(OperationsError::ZeroAmount {})
+        );
+        assert_eq!(
+            AmountSpec::Ratio(Decimal::zero()).apply(Uint128::new(1000)),
+            Err(OperationsError::ZeroAmount {})
+        );
+        assert_eq!(
+            AmountSpec::Capped(Uint128::zero()).apply(Uint128::new(1000)),
+            Err(OperationsError::ZeroAmount {})
+        );
+    }
+
+    #[test]
+    fn bare_amount_reads_as_exact() {
+        let spec: AmountSpec = from_slice(br#""100""#).unwrap();
+        assert_eq!(spec, AmountSpec::Exact(Uint128::new(100)));
+
+        let spec: AmountSpec = from_slice(br#"{"exact":"100"}"#).unwrap();
+        assert_eq!(spec, AmountSpec::Exact(Uint128::new(100)));
+        let spec: AmountSpec = from_slice(br#"{"ratio":"0.5"}"#).unwrap();
+        assert_eq!(spec, AmountSpec::Ratio(Decimal::percent(50)));
+        let spec: AmountSpec = from_slice(br#""all""#).unwrap();
+        assert_eq!(spec, AmountSpec::All);
+
+        assert!(from_slice::<AmountSpec>(br#"{"half":"100"}"#).is_err());
+        assert_eq!(
+            to_vec(&AmountSpec::Exact(Uint128::new(100))).unwrap(),
+            br#"{"exact":"100"}"#.to_vec()
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use cosmwasm_std::{Decimal, StdError, Uint128};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum OperationsError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Either amount or sender must be provided")]
+    AmountOrSenderRequired {},
+
+    #[error("No delegation to validator {validator}")]
+    NoDelegation { validator: String },
+
+    #[error("{delegator} has no delegations")]
+    NoDelegations { delegator: String },
+
+    #[error("{holder} has no {token} balance")]
+    ZeroBalance { token: String, holder: String },
+
+    #[error("Ratio {ratio} must not exceed 1")]
+    InvalidRatio { ratio: Decimal },
+
+    #[error("Amount resolves to zero")]
+    ZeroAmount {},
+
+    #[error("Reserve {reserve} exceeds balance {balance}")]
+    ReserveExceedsBalance { reserve: Uint128, balance: Uint128 },
+}
+
+/// Operation builders return `StdResult`, so typed errors are carried as a
+/// generic error with their display message.
+impl From<OperationsError> for StdError {
+    fn from(err: OperationsError) -> Self {
+        match err {
+            OperationsError::Std(err) => err,
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+pub mod amount;
+pub mod error;
+pub mod macros;
+pub mod operation;
+pub mod ownership;
+
+pub use crate::amount::{AmountSource, AmountSpec};
+pub use crate::error::OperationsError;
+pub use crate::operation::Operation;
+pub use crate::ownership::OwnedConfig;
@@ -0,0 +1,85 @@
+/// Implement `OwnedConfig` for a config struct with an `owner: CanonicalAddr` field.
+#[macro_export]
+macro_rules! impl_owned_config {
+    ($config:ty) => {
+        impl $crate::ownership::OwnedConfig for $config {
+            fn owner(&self) -> &cosmwasm_std::CanonicalAddr {
+                &self.owner
+            }
+
+            fn set_owner(&mut self, owner: cosmwasm_std::CanonicalAddr) {
+                self.owner = owner;
+            }
+        }
+    };
+}
+
+/// Generate the `instantiate` entry point of an operations contract, storing
+/// the config built from the instantiate message.
+///
+/// `$config` is the contract's `Item<Config>` and `$to_config` a
+/// `fn(&dyn Api, InstantiateMsg) -> StdResult<Config>`.
+#[macro_export]
+macro_rules! instantiate_entry_point {
+    ($instantiate_msg:ty, $config:path, $to_config:path) => {
+        #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+        pub fn instantiate(
+            deps: cosmwasm_std::DepsMut,
+            _env: cosmwasm_std::Env,
+            _info: cosmwasm_std::MessageInfo,
+            msg: $instantiate_msg,
+        ) -> cosmwasm_std::StdResult<cosmwasm_std::Response> {
+            let config = $to_config(deps.api, msg)?;
+            $config.save(deps.storage, &config)?;
+            Ok(cosmwasm_std::Response::default())
+        }
+    };
+}
+
+/// Generate the no-op `migrate` entry point of an operations contract.
+#[macro_export]
+macro_rules! migrate_entry_point {
+    ($migrate_msg:ty) => {
+        #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+        pub fn migrate(
+            _deps: cosmwasm_std::DepsMut,
+            _env: cosmwasm_std::Env,
+            _msg: $migrate_msg,
+        ) -> cosmwasm_std::StdResult<cosmwasm_std::Response> {
+            Ok(cosmwasm_std::Response::default())
+        }
+    };
+}
+
+/// Generate the `query` entry point of an adapter whose only queries are
+/// `QueryMsg::Messages { msg }`, `QueryMsg::BatchMessages { msgs }` and
+/// `QueryMsg::Config {}`.
+///
+/// `$operation` is the adapter's operation enum and `$query_config` a
+/// `fn(Deps) -> StdResult<ConfigResponse>`.
+#[macro_export]
+macro_rules! query_entry_point {
+    ($query_msg:ident, $operation:ty, $query_config:path) => {
+        #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+        pub fn query(
+            deps: cosmwasm_std::Deps,
+            env: cosmwasm_std::Env,
+            msg: $query_msg,
+        ) -> cosmwasm_std::StdResult<cosmwasm_std::Binary> {
+            match msg {
+                $query_msg::Config {} => cosmwasm_std::to_binary(&$query_config(deps)?),
+                $query_msg::Messages { msg } => cosmwasm_std::to_binary(
+                    &$crate::operation::build_messages::<$operation>(deps, env, &msg)?,
+                ),
+                $query_msg::BatchMessages { msgs } => {
+                    cosmwasm_std::to_binary(&$crate::operation::build_batch_messages(
+                        deps,
+                        env,
+                        &msgs,
+                        $crate::operation::build_messages::<$operation>,
+                    )?)
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,173 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{from_binary, Binary, CosmosMsg, Deps, Env, StdError, StdResult};
+use serde::de::{DeserializeOwned, Error};
+use serde::{Deserialize, Deserializer};
+
+/// An operation an operations contract knows how to turn into messages.
+///
+/// Implemented by the operation enum of each adapter: `decode` reads it from
+/// the `msg` of `QueryMsg::Messages` and `build` returns the messages the
+/// executor has to dispatch for it.
+pub trait Operation: DeserializeOwned {
+    fn decode(msg: &Binary) -> StdResult<Self> {
+        decode_operation(msg)
+    }
+
+    fn build(self, deps: Deps, env: Env) -> StdResult<Vec<CosmosMsg>>;
+}
+
+/// Wraps a value so that deserialization errors carry the path of the field
+/// that failed, e.g. `token.token_transfer.amount`.
+struct WithPath<T>(T);
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for WithPath<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        serde_path_to_error::deserialize(deserializer)
+            .map(WithPath)
+            .map_err(|err| {
+                let path = err.path().to_string();
+                D::Error::custom(format!("{} at `{}`", err.into_inner(), path))
+            })
+    }
+}
+
+/// Decode an operation json, reporting the operation type and the path of the
+/// offending field on failure.
+pub fn decode_operation<O: DeserializeOwned>(msg: &Binary) -> StdResult<O> {
+    match from_binary::<WithPath<O>>(msg) {
+        Ok(WithPath(operation)) => Ok(operation),
+        Err(StdError::ParseErr { msg, .. }) => {
+            let target = std::any::type_name::<O>();
+            let target = target.rsplit("::").next().unwrap_or(target);
+            Err(StdError::parse_err(target, msg))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Decode `msg` as `O` and build its messages.
+pub fn build_messages<O: Operation>(
+    deps: Deps,
+    env: Env,
+    msg: &Binary,
+) -> StdResult<Vec<CosmosMsg>> {
+    O::decode(msg)?.build(deps, env)
+}
+
+#[cw_serde]
+pub struct BatchMessagesResponse {
+    pub messages: Vec<CosmosMsg>,
+    /// End (exclusive) of each step in `messages`: step `i` produced
+    /// `messages[boundaries[i - 1]..boundaries[i]]`, step 0 starts at 0.
+    pub boundaries: Vec<u32>,
+}
+
+/// Build every step of a recipe with `build` and concatenate the messages.
+///
+/// Fails on the first step that cannot be decoded or built, reporting its index.
+pub fn build_batch_messages<F>(
+    deps: Deps,
+    env: Env,
+    msgs: &[Binary],
+    build: F,
+) -> StdResult<BatchMessagesResponse>
+where
+    F: Fn(Deps, Env, &Binary) -> StdResult<Vec<CosmosMsg>>,
+{
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut boundaries: Vec<u32> = vec![];
+
+    for (index, msg) in msgs.iter().enumerate() {
+        let step_messages = build(deps, env.clone(), msg)
+            .map_err(|err| StdError::generic_err(format!("Step {}: {}", index, err)))?;
+        messages.extend(step_messages);
+        boundaries.push(messages.len() as u32);
+    }
+
+    Ok(BatchMessagesResponse {
+        messages,
+        boundaries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{coins, to_binary, BankMsg};
+
+    #[cw_serde]
+    enum TestOperation {
+        Send { count: u8 },
+        Fail {},
+    }
+
+    impl Operation for TestOperation {
+        fn build(self, _deps: Deps, _env: Env) -> StdResult<Vec<CosmosMsg>> {
+            match self {
+                TestOperation::Send { count } => Ok((0..count)
+                    .map(|_| {
+                        CosmosMsg::Bank(BankMsg::Send {
+                            to_address: "recipient".to_string(),
+                            amount: coins(1, "orai"),
+                        })
+                    })
+                    .collect()),
+                TestOperation::Fail {} => Err(StdError::generic_err("boom")),
+            }
+        }
+    }
+
+    fn batch(steps: &[TestOperation]) -> StdResult<BatchMessagesResponse> {
+        let deps = mock_dependencies();
+        let msgs = steps.iter().map(to_binary).collect::<StdResult<Vec<_>>>()?;
+        build_batch_messages(
+            deps.as_ref(),
+            mock_env(),
+            &msgs,
+            build_messages::<TestOperation>,
+        )
+    }
+
+    #[test]
+    fn batch_boundaries() {
+        let res = batch(&[
+            TestOperation::Send { count: 2 },
+            TestOperation::Send { count: 0 },
+            TestOperation::Send { count: 1 },
+        ])
+        .unwrap();
+        assert_eq!(res.messages.len(), 3);
+        assert_eq!(res.boundaries, vec![2, 2, 3]);
+
+        let res = batch(&[]).unwrap();
+        assert!(res.messages.is_empty());
+        assert!(res.boundaries.is_empty());
+    }
+
+    #[test]
+    fn batch_errors_name_the_step() {
+        let err = batch(&[TestOperation::Send { count: 1 }, TestOperation::Fail {}]).unwrap_err();
+        assert_eq!(err, StdError::generic_err("Step 1: Generic error: boom"));
+
+        let deps = mock_dependencies();
+        let msgs = vec![
+            to_binary(&TestOperation::Send { count: 1 }).unwrap(),
+            Binary::from(br#"{"send":{"count":"many"}}"#.to_vec()),
+        ];
+        let err = build_batch_messages(
+            deps.as_ref(),
+            mock_env(),
+            &msgs,
+            build_messages::<TestOperation>,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.starts_with("Generic error: Step 1: "), "{}", err);
+        assert!(err.contains("TestOperation"), "{}", err);
+        assert!(err.contains("at `send.count`"), "{}", err);
+    }
+}
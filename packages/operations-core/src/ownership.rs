@@ -0,0 +1,54 @@
+use cosmwasm_std::{Addr, Api, CanonicalAddr, DepsMut, MessageInfo, Response, StdResult};
+use cw_storage_plus::Item;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::error::OperationsError;
+
+/// Config of an operations contract, owned by a single address.
+///
+/// Use `impl_owned_config!` for configs storing the owner in an `owner` field.
+pub trait OwnedConfig {
+    fn owner(&self) -> &CanonicalAddr;
+    fn set_owner(&mut self, owner: CanonicalAddr);
+}
+
+pub fn assert_owner<C: OwnedConfig>(
+    api: &dyn Api,
+    config: &C,
+    sender: &Addr,
+) -> Result<(), OperationsError> {
+    if api.addr_humanize(config.owner())? != *sender {
+        return Err(OperationsError::Unauthorized {});
+    }
+
+    Ok(())
+}
+
+/// Owner-gated config update shared by every `ExecuteMsg::UpdateConfig`.
+///
+/// Transfers ownership when `owner` is given, lets `update` apply the
+/// contract specific fields and saves the result.
+pub fn update_config<C, F>(
+    deps: DepsMut,
+    info: &MessageInfo,
+    store: &Item<C>,
+    owner: Option<Addr>,
+    update: F,
+) -> Result<Response, OperationsError>
+where
+    C: OwnedConfig + Serialize + DeserializeOwned,
+    F: FnOnce(&dyn Api, &mut C) -> StdResult<()>,
+{
+    let mut config = store.load(deps.storage)?;
+    assert_owner(deps.api, &config, &info.sender)?;
+
+    if let Some(owner) = owner {
+        config.set_owner(deps.api.addr_canonicalize(owner.as_str())?);
+    }
+
+    update(deps.api, &mut config)?;
+
+    store.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
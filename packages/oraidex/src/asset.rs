@@ -297,6 +297,38 @@ pub struct PairInfo {
     pub commission_rate: String,
 }
 
+impl PairInfo {
+    pub fn query_pools(
+        &self,
+        querier: &QuerierWrapper,
+        contract_addr: Addr,
+    ) -> StdResult<[Asset; 2]> {
+        Ok([
+            Asset {
+                amount: self.asset_infos[0].query_pool(querier, contract_addr.clone())?,
+                info: self.asset_infos[0].clone(),
+            },
+            Asset {
+                amount: self.asset_infos[1].query_pool(querier, contract_addr)?,
+                info: self.asset_infos[1].clone(),
+            },
+        ])
+    }
+
+    pub fn to_raw(&self, api: &dyn Api) -> StdResult<PairInfoRaw> {
+        Ok(PairInfoRaw {
+            asset_infos: [
+                self.asset_infos[0].to_raw(api)?,
+                self.asset_infos[1].to_raw(api)?,
+            ],
+            contract_addr: api.addr_canonicalize(self.contract_addr.as_str())?,
+            liquidity_token: api.addr_canonicalize(self.liquidity_token.as_str())?,
+            oracle_addr: api.addr_canonicalize(self.oracle_addr.as_str())?,
+            commission_rate: self.commission_rate.clone(),
+        })
+    }
+}
+
 #[cw_serde]
 pub struct PairInfoRaw {
     pub asset_infos: [AssetInfoRaw; 2],
@@ -0,0 +1,13 @@
+use cosmwasm_schema::cw_serde;
+
+use crate::asset::{AssetInfo, PairInfo};
+
+#[cw_serde]
+pub enum FactoryQueryMsg {
+    Pair { asset_infos: [AssetInfo; 2] },
+}
+
+#[cw_serde]
+pub struct PairsResponse {
+    pub pairs: Vec<PairInfo>,
+}
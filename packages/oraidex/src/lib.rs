@@ -1,6 +1,8 @@
 pub mod asset;
+pub mod factory;
 pub mod oracle;
 pub mod pair;
 pub mod querier;
 pub mod router;
 pub mod staking;
+pub mod tax;
@@ -2,8 +2,8 @@ use cosmwasm_schema::serde::de::DeserializeOwned;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 
 use cosmwasm_std::{
-    to_binary, Addr, Api, CanonicalAddr, CosmosMsg, Decimal, QuerierWrapper, StdResult, Uint128,
-    WasmMsg,
+    to_binary, Addr, Api, CanonicalAddr, CosmosMsg, Decimal, QuerierWrapper, StdError, StdResult,
+    Uint128, WasmMsg,
 };
 
 #[cw_serde]
@@ -163,6 +163,13 @@ pub struct MigrateMsg {}
 pub struct OracleContract(pub Addr);
 
 impl OracleContract {
+    /// The oracle an operations contract has configured, failing when unset
+    pub fn from_config(api: &dyn Api, oracle: Option<&CanonicalAddr>) -> StdResult<Self> {
+        let oracle =
+            oracle.ok_or_else(|| StdError::generic_err("Oraiswap oracle is not configured"))?;
+        Ok(OracleContract(api.addr_humanize(oracle)?))
+    }
+
     pub fn addr(&self) -> Addr {
         self.0.clone()
     }
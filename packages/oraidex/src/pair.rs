@@ -1,7 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Decimal};
 
-use crate::asset::Asset;
+use crate::asset::{Asset, PairInfo};
 
 #[cw_serde]
 pub enum PairExecuteMsg {
@@ -12,3 +12,13 @@ pub enum PairExecuteMsg {
     },
     WithdrawLiquidity {},
 }
+
+#[cw_serde]
+pub enum PairQueryMsg {
+    Pair {},
+}
+
+#[cw_serde]
+pub struct PairResponse {
+    pub info: PairInfo,
+}
@@ -1,15 +1,12 @@
-// use crate::asset::{Asset, AssetInfo, PairInfo};
-// use crate::factory::{ConfigResponse, QueryMsg as FactoryQueryMsg};
-// use crate::pair::{
-//     PairResponse, QueryMsg as PairQueryMsg, ReverseSimulationResponse, SimulationResponse,
-// };
-
 use cosmwasm_std::{Addr, QuerierWrapper, StdResult, Uint128};
 use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
 
 use crate::{
-    asset::AssetInfo,
-    staking::{RewardInfoResponse, StakingQueryMsg},
+    asset::{Asset, AssetInfo, PairInfo},
+    factory::FactoryQueryMsg,
+    pair::{PairQueryMsg, PairResponse},
+    router::{OraiswapQueryMsg, SimulateSwapOperationsResponse, SwapOperation},
+    staking::{RewardInfoResponse, RewardInfoResponseItem, RewardsPerSecResponse, StakingQueryMsg},
 };
 
 pub fn query_token_balance(
@@ -55,75 +52,79 @@ pub fn query_staking_amount(
         },
     )?;
 
-    Ok(res.reward_infos[0].bond_amount)
+    // stakers without a position in the pool get no entry
+    Ok(res
+        .reward_infos
+        .first()
+        .map(|reward_info| reward_info.bond_amount)
+        .unwrap_or_default())
 }
 
-// pub fn query_pair_info(
-//     querier: &QuerierWrapper,
-//     factory_addr: Addr,
-//     asset_infos: &[AssetInfo; 2],
-// ) -> StdResult<PairInfo> {
-//     querier.query_wasm_smart(
-//         factory_addr,
-//         &FactoryQueryMsg::Pair {
-//             asset_infos: asset_infos.clone(),
-//         },
-//     )
-// }
+pub fn query_reward_info(
+    querier: &QuerierWrapper,
+    orai_staking_contract: Addr,
+    account_addr: Addr,
+    asset_info: AssetInfo,
+) -> StdResult<Option<RewardInfoResponseItem>> {
+    let res: RewardInfoResponse = querier.query_wasm_smart(
+        orai_staking_contract,
+        &StakingQueryMsg::RewardInfo {
+            staker_addr: account_addr,
+            asset_info: Some(asset_info),
+        },
+    )?;
 
-// pub fn query_pair_config(
-//     querier: &QuerierWrapper,
-//     factory_addr: Addr,
-// ) -> StdResult<ConfigResponse> {
-//     querier.query_wasm_smart(factory_addr, &FactoryQueryMsg::Config {})
-// }
+    // stakers without a position in the pool get no entry
+    Ok(res.reward_infos.into_iter().next())
+}
 
-// pub fn simulate(
-//     querier: &QuerierWrapper,
-//     pair_addr: Addr,
-//     offer_asset: &Asset,
-// ) -> StdResult<SimulationResponse> {
-//     querier.query_wasm_smart(
-//         pair_addr,
-//         &PairQueryMsg::Simulation {
-//             offer_asset: offer_asset.clone(),
-//         },
-//     )
-// }
+pub fn query_rewards_per_sec(
+    querier: &QuerierWrapper,
+    orai_staking_contract: Addr,
+    asset_info: AssetInfo,
+) -> StdResult<Vec<Asset>> {
+    let res: RewardsPerSecResponse = querier.query_wasm_smart(
+        orai_staking_contract,
+        &StakingQueryMsg::RewardsPerSec { asset_info },
+    )?;
 
-// pub fn reverse_simulate(
-//     querier: &QuerierWrapper,
-//     pair_addr: Addr,
-//     ask_asset: &Asset,
-// ) -> StdResult<ReverseSimulationResponse> {
-//     querier.query_wasm_smart(
-//         pair_addr,
-//         &PairQueryMsg::ReverseSimulation {
-//             ask_asset: ask_asset.clone(),
-//         },
-//     )
-// }
+    Ok(res.assets)
+}
 
-// pub fn query_pair_info_from_pair(
-//     querier: &QuerierWrapper,
-//     pair_contract: Addr,
-// ) -> StdResult<PairInfo> {
-//     let res: PairResponse = querier.query_wasm_smart(pair_contract, &PairQueryMsg::Pair {})?;
-//     Ok(res.info)
-// }
+pub fn simulate_swap_operations(
+    querier: &QuerierWrapper,
+    router_contract: Addr,
+    offer_amount: Uint128,
+    operations: Vec<SwapOperation>,
+) -> StdResult<Uint128> {
+    let res: SimulateSwapOperationsResponse = querier.query_wasm_smart(
+        router_contract,
+        &OraiswapQueryMsg::SimulateSwapOperations {
+            offer_amount,
+            operations,
+        },
+    )?;
 
-// // upper bound key by 1, for Order::Ascending
-// pub fn calc_range_start(start_after: Option<Vec<u8>>) -> Option<Vec<u8>> {
-//     start_after.map(|mut input| {
-//         // zero out all trailing 255, increment first that is not such
-//         for i in (0..input.len()).rev() {
-//             if input[i] == 255 {
-//                 input[i] = 0;
-//             } else {
-//                 input[i] += 1;
-//                 break;
-//             }
-//         }
-//         input
-//     })
-// }
+    Ok(res.amount)
+}
+
+pub fn query_pair_info(
+    querier: &QuerierWrapper,
+    factory_addr: Addr,
+    asset_infos: &[AssetInfo; 2],
+) -> StdResult<PairInfo> {
+    querier.query_wasm_smart(
+        factory_addr,
+        &FactoryQueryMsg::Pair {
+            asset_infos: asset_infos.clone(),
+        },
+    )
+}
+
+pub fn query_pair_info_from_pair(
+    querier: &QuerierWrapper,
+    pair_contract: Addr,
+) -> StdResult<PairInfo> {
+    let res: PairResponse = querier.query_wasm_smart(pair_contract, &PairQueryMsg::Pair {})?;
+    Ok(res.info)
+}
@@ -39,3 +39,16 @@ pub enum OraiswapExecuteMsg {
 
     WithdrawLiquidity {},
 }
+
+#[cw_serde]
+pub enum OraiswapQueryMsg {
+    SimulateSwapOperations {
+        offer_amount: Uint128,
+        operations: Vec<SwapOperation>,
+    },
+}
+
+#[cw_serde]
+pub struct SimulateSwapOperationsResponse {
+    pub amount: Uint128,
+}
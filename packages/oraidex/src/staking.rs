@@ -24,6 +24,14 @@ pub enum StakingQueryMsg {
         staker_addr: Addr,
         asset_info: Option<AssetInfo>,
     },
+    RewardsPerSec {
+        asset_info: AssetInfo,
+    },
+}
+
+#[cw_serde]
+pub struct RewardsPerSecResponse {
+    pub assets: Vec<Asset>,
 }
 
 #[cw_serde]
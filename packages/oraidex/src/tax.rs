@@ -0,0 +1,93 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, CosmosMsg, Decimal, QuerierWrapper, StdResult, Uint128, WasmMsg};
+
+use crate::{
+    asset::{Asset, AssetInfo, ORAI_DENOM},
+    oracle::OracleContract,
+};
+
+#[cw_serde]
+pub struct TaxResponse {
+    pub tax: Uint128,
+    pub amount_after_tax: Uint128,
+}
+
+#[cw_serde]
+pub struct MessagesWithTaxResponse {
+    pub messages: Vec<CosmosMsg>,
+    /// Tax the chain charges on the native coins the messages move, per denom
+    pub tax: Vec<Coin>,
+}
+
+/// Split of `asset` into the tax the chain charges to transfer it all and
+/// what is left to transfer, nothing for cw20.
+pub fn query_tax(
+    querier: &QuerierWrapper,
+    oracle: &OracleContract,
+    asset: &Asset,
+) -> StdResult<TaxResponse> {
+    let tax = asset.compute_tax(oracle, querier)?;
+    Ok(TaxResponse {
+        tax,
+        amount_after_tax: asset.amount.checked_sub(tax)?,
+    })
+}
+
+/// `amount` of `asset_info` less the transfer tax when `deduct_tax` is set.
+pub fn amount_after_tax(
+    querier: &QuerierWrapper,
+    oracle: impl FnOnce() -> StdResult<OracleContract>,
+    asset_info: &AssetInfo,
+    amount: Uint128,
+    deduct_tax: bool,
+) -> StdResult<Uint128> {
+    if !deduct_tax || !asset_info.is_native_token() {
+        return Ok(amount);
+    }
+    let asset = Asset {
+        info: asset_info.clone(),
+        amount,
+    };
+    Ok(query_tax(querier, &oracle()?, &asset)?.amount_after_tax)
+}
+
+/// Tax charged on top of every native coin sent by `messages`, summed per denom.
+pub fn query_messages_tax(
+    querier: &QuerierWrapper,
+    oracle: &OracleContract,
+    messages: &[CosmosMsg],
+) -> StdResult<Vec<Coin>> {
+    let mut tax_rate: Option<Decimal> = None;
+    let mut taxes: Vec<Coin> = vec![];
+    for coin in messages.iter().flat_map(sent_coins) {
+        if coin.denom == ORAI_DENOM || coin.amount.is_zero() {
+            continue;
+        }
+        let rate = match tax_rate {
+            Some(rate) => rate,
+            None => *tax_rate.insert(oracle.query_tax_rate(querier)?.rate),
+        };
+        let cap = oracle.query_tax_cap(querier, coin.denom.as_str())?.cap;
+        let tax = std::cmp::min(coin.amount * rate, cap);
+        if tax.is_zero() {
+            continue;
+        }
+        match taxes.iter_mut().find(|total| total.denom == coin.denom) {
+            Some(total) => total.amount += tax,
+            None => taxes.push(Coin {
+                denom: coin.denom.clone(),
+                amount: tax,
+            }),
+        }
+    }
+    Ok(taxes)
+}
+
+fn sent_coins(msg: &CosmosMsg) -> &[Coin] {
+    match msg {
+        CosmosMsg::Bank(cosmwasm_std::BankMsg::Send { amount, .. }) => amount,
+        CosmosMsg::Wasm(WasmMsg::Execute { funds, .. })
+        | CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. }) => funds,
+        _ => &[],
+    }
+}
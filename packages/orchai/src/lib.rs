@@ -1,6 +1,7 @@
 pub mod custody;
 pub mod liquidation;
 pub mod market;
+pub mod oracle;
 pub mod orai_staking;
 pub mod overseer;
 pub mod querier;
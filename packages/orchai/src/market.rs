@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Uint256;
+use cosmwasm_std::{Decimal256, Uint256};
 
 #[cw_serde]
 pub enum MarketExecuteMsg {
@@ -20,6 +20,23 @@ pub enum MarketExecuteMsg {
     },
 }
 
+#[cw_serde]
+pub enum MarketQueryMsg {
+    BorrowerInfo {
+        borrower: String,
+        block_height: Option<u64>,
+    },
+}
+
+#[cw_serde]
+pub struct BorrowerInfoResponse {
+    pub borrower: String,
+    pub interest_index: Decimal256,
+    pub reward_index: Decimal256,
+    pub loan_amount: Uint256,
+    pub pending_rewards: Decimal256,
+}
+
 #[cw_serde]
 pub enum AStableRewardExecuteMsg {
     ClaimRewards { recipient: Option<String> },
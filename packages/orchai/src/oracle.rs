@@ -0,0 +1,14 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Decimal256;
+
+#[cw_serde]
+pub enum OracleQueryMsg {
+    Price { base: String, quote: String },
+}
+
+#[cw_serde]
+pub struct PriceResponse {
+    pub rate: Decimal256,
+    pub last_updated_base: u64,
+    pub last_updated_quote: u64,
+}
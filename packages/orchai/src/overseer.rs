@@ -1,4 +1,5 @@
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal256, Uint256};
 
 use crate::tokens::TokensHuman;
 
@@ -11,3 +12,36 @@ pub enum OverseerExecuteMsg {
         collaterals: TokensHuman, // <(Collateral Token, Amount)>
     },
 }
+
+#[cw_serde]
+pub enum OverseerQueryMsg {
+    Whitelist {
+        collateral_token: Option<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    BorrowLimit {
+        borrower: String,
+        block_time: Option<u64>,
+    },
+}
+
+#[cw_serde]
+pub struct WhitelistResponseElem {
+    pub name: String,
+    pub symbol: String,
+    pub max_ltv: Decimal256,
+    pub custody_contract: String,
+    pub collateral_token: String,
+}
+
+#[cw_serde]
+pub struct BorrowLimitResponse {
+    pub borrower: String,
+    pub borrow_limit: Uint256,
+}
+
+#[cw_serde]
+pub struct WhitelistResponse {
+    pub elems: Vec<WhitelistResponseElem>,
+}
@@ -1,7 +1,10 @@
-use cosmwasm_std::{Addr, QuerierWrapper, StdResult, Uint128};
+use cosmwasm_std::{Addr, Decimal256, QuerierWrapper, StdError, StdResult, Uint128, Uint256};
 use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
 
 use crate::custody::{BorrowerResponse, CustodyQueryMsg};
+use crate::market::{BorrowerInfoResponse, MarketQueryMsg};
+use crate::oracle::{OracleQueryMsg, PriceResponse};
+use crate::overseer::{BorrowLimitResponse, OverseerQueryMsg, WhitelistResponse};
 pub fn query_token_balance(
     querier: &QuerierWrapper,
     contract_addr: Addr,
@@ -32,3 +35,75 @@ pub fn query_custody_borrower(
 
     Ok(res)
 }
+
+pub fn query_max_ltv(
+    querier: &QuerierWrapper,
+    overseer_contract: Addr,
+    collateral_token: Addr,
+) -> StdResult<Decimal256> {
+    let res: WhitelistResponse = querier.query_wasm_smart(
+        overseer_contract,
+        &OverseerQueryMsg::Whitelist {
+            collateral_token: Some(collateral_token.to_string()),
+            start_after: None,
+            limit: None,
+        },
+    )?;
+
+    res.elems
+        .into_iter()
+        .find(|elem| elem.collateral_token == collateral_token.as_str())
+        .map(|elem| elem.max_ltv)
+        .ok_or_else(|| {
+            StdError::generic_err(format!(
+                "{} is not whitelisted by the overseer",
+                collateral_token
+            ))
+        })
+}
+
+pub fn query_borrow_limit(
+    querier: &QuerierWrapper,
+    overseer_contract: Addr,
+    borrower: Addr,
+    block_time: u64,
+) -> StdResult<Uint256> {
+    let res: BorrowLimitResponse = querier.query_wasm_smart(
+        overseer_contract,
+        &OverseerQueryMsg::BorrowLimit {
+            borrower: borrower.to_string(),
+            block_time: Some(block_time),
+        },
+    )?;
+
+    Ok(res.borrow_limit)
+}
+
+pub fn query_loan_amount(
+    querier: &QuerierWrapper,
+    market_contract: Addr,
+    borrower: Addr,
+    block_height: u64,
+) -> StdResult<Uint256> {
+    let res: BorrowerInfoResponse = querier.query_wasm_smart(
+        market_contract,
+        &MarketQueryMsg::BorrowerInfo {
+            borrower: borrower.to_string(),
+            block_height: Some(block_height),
+        },
+    )?;
+
+    Ok(res.loan_amount)
+}
+
+pub fn query_price(
+    querier: &QuerierWrapper,
+    oracle_contract: Addr,
+    base: String,
+    quote: String,
+) -> StdResult<Decimal256> {
+    let res: PriceResponse =
+        querier.query_wasm_smart(oracle_contract, &OracleQueryMsg::Price { base, quote })?;
+
+    Ok(res.rate)
+}